@@ -14,6 +14,15 @@ macro_rules! binwrite_impl {
                             writer.write_all(&self.to_le_bytes())
                         }
                         Endian::Native => {
+                            if options.deny_native && std::mem::size_of::<$type_name>() > 1 {
+                                return Err(std::io::Error::new(
+                                    std::io::ErrorKind::InvalidInput,
+                                    concat!(
+                                        "writing ", stringify!($type_name),
+                                        " with native endianness is disabled by the deny_native option"
+                                    )
+                                ));
+                            }
                             if cfg!(target_endian = "little") {
                                 writer.write_all(&self.to_le_bytes())
                             } else {
@@ -29,28 +38,32 @@ macro_rules! binwrite_impl {
 
 impl BinWrite for char {
     fn write_options<W: Write>(&self, writer: &mut W, _options: &WriterOption) -> Result<()> {
-        // This is a bad impl but idk how encode_utf8 works
-        writer.write_all(self.to_string().as_bytes())
+        let mut buf = [0u8; 4];
+        writer.write_all(self.encode_utf8(&mut buf).as_bytes())
     }
 }
 
-binwrite_impl!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64);
+binwrite_impl!(u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64);
+
+impl BinWrite for u8 {
+    fn write_options<W: Write>(&self, writer: &mut W, _options: &WriterOption) -> Result<()> {
+        writer.write_all(&[*self])
+    }
+
+    fn write_slice<W: Write>(items: &[u8], writer: &mut W, _options: &WriterOption) -> Result<()> {
+        writer.write_all(items)
+    }
+}
 
 impl<B: BinWrite> BinWrite for Vec<B> {
     fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
-        for item in self {
-            BinWrite::write_options(item, writer, options)?;
-        }
-        Ok(())
+        B::write_slice(self, writer, options)
     }
 }
 
 impl<B: BinWrite> BinWrite for [B] {
     fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
-        for item in &self[..] {
-            BinWrite::write_options(item, writer, options)?;
-        }
-        Ok(())
+        B::write_slice(self, writer, options)
     }
 }
 
@@ -59,10 +72,7 @@ macro_rules! binwrite_array_impl {
         $(
             impl<B: BinWrite> BinWrite for [B; $size] {
                 fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
-                    for item in &self[..] {
-                        BinWrite::write_options(item, writer, options)?;
-                    }
-                    Ok(())
+                    B::write_slice(&self[..], writer, options)
                 }
             }
         )*
@@ -71,27 +81,124 @@ macro_rules! binwrite_array_impl {
 
 binwrite_array_impl!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20);
 
-impl BinWrite for &[u8] {
+impl BinWrite for str {
     fn write_options<W: Write>(&self, writer: &mut W, _options: &WriterOption) -> Result<()> {
-        writer.write_all(self)
+        writer.write_all(self.as_bytes())
     }
 }
 
-impl BinWrite for str {
-    fn write_options<W: Write>(&self, writer: &mut W, _options: &WriterOption) -> Result<()> {
-        writer.write_all(self.as_bytes())
+impl<B: BinWrite + ?Sized> BinWrite for &B {
+    const ALIGNMENT: usize = B::ALIGNMENT;
+
+    fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+        (**self).write_options(writer, options)
     }
 }
 
-impl BinWrite for &str {
-    fn write_options<W: Write>(&self, writer: &mut W, _options: &WriterOption) -> Result<()> {
-        writer.write_all((*self).as_bytes())
+impl<B: BinWrite + ?Sized> BinWrite for &mut B {
+    const ALIGNMENT: usize = B::ALIGNMENT;
+
+    fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+        (**self).write_options(writer, options)
+    }
+}
+
+impl<B: BinWrite + ?Sized> BinWrite for Box<B> {
+    const ALIGNMENT: usize = B::ALIGNMENT;
+
+    fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+        (**self).write_options(writer, options)
+    }
+}
+
+impl<B: BinWrite + ?Sized> BinWrite for std::rc::Rc<B> {
+    const ALIGNMENT: usize = B::ALIGNMENT;
+
+    fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+        (**self).write_options(writer, options)
+    }
+}
+
+impl<B: BinWrite + ?Sized> BinWrite for std::sync::Arc<B> {
+    const ALIGNMENT: usize = B::ALIGNMENT;
+
+    fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+        (**self).write_options(writer, options)
     }
 }
 
-impl<B: BinWrite> BinWrite for &B {
+/// Lets preprocessors return borrowed data when no change is needed and only allocate otherwise
+impl<B: BinWrite + ToOwned + ?Sized> BinWrite for std::borrow::Cow<'_, B> {
+    const ALIGNMENT: usize = B::ALIGNMENT;
+
     fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
-        (*self).write_options(writer, options)
+        (**self).write_options(writer, options)
+    }
+}
+
+/// Map entries are written as key then value. B-tree maps and sets are written in sorted order.
+impl<K: BinWrite, V: BinWrite> BinWrite for std::collections::BTreeMap<K, V> {
+    fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+        for (key, value) in self {
+            BinWrite::write_options(key, writer, options)?;
+            BinWrite::write_options(value, writer, options)?;
+        }
+        Ok(())
+    }
+}
+
+impl<K: BinWrite> BinWrite for std::collections::BTreeSet<K> {
+    fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+        for key in self {
+            BinWrite::write_options(key, writer, options)?;
+        }
+        Ok(())
+    }
+}
+
+/// Write map entries or set items in iteration order, or sorted by the bytes of each key when
+/// [canonical](WriterOption::canonical) is set
+fn write_unordered<'a, K, V, I, W>(entries: I, writer: &mut W, options: &WriterOption) -> Result<()>
+    where K: BinWrite + 'a,
+          V: BinWrite + 'a,
+          I: Iterator<Item = (&'a K, &'a V)>,
+          W: Write,
+{
+    if !options.canonical {
+        for (key, value) in entries {
+            BinWrite::write_options(key, writer, options)?;
+            BinWrite::write_options(value, writer, options)?;
+        }
+        return Ok(());
+    }
+
+    let mut sorted = entries
+        .map(|(key, value)| {
+            let mut bytes = vec![];
+            BinWrite::write_options(key, &mut bytes, options)?;
+            Ok((bytes, value))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    sorted.sort_by(|(a, _), (b, _)| a.cmp(b));
+    for (key, value) in sorted {
+        writer.write_all(&key)?;
+        BinWrite::write_options(value, writer, options)?;
+    }
+    Ok(())
+}
+
+/// Entries are written as key then value, in iteration order unless
+/// [canonical](WriterOption::canonical) is set
+impl<K: BinWrite, V: BinWrite, S> BinWrite for std::collections::HashMap<K, V, S> {
+    fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+        write_unordered(self.iter(), writer, options)
+    }
+}
+
+/// Items are written in iteration order unless [canonical](WriterOption::canonical) is set
+impl<K: BinWrite, S> BinWrite for std::collections::HashSet<K, S> {
+    fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+        write_unordered(self.iter().map(|key| (key, &())), writer, options)
     }
 }
 
@@ -101,34 +208,39 @@ impl BinWrite for String {
     }
 }
 
-/// Internal macro to recursively implement BinWrite for every size tuple 0 to 20
+/// Internal macro to implement BinWrite for every size tuple 1 to 32. Each step of the
+/// recursion implements BinWrite for the items accumulated so far, then adds the next item.
 macro_rules! binwrite_tuple_impl {
-    ($type1:ident $(, $types:ident)*) => {
-        #[allow(non_camel_case_types)]
-        impl<$type1: BinWrite, $($types: BinWrite),*> BinWrite for ($type1, $($types),*) {
-            paste::item!{
-                fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
-                        let (_, $([<item_ $types>]),*) = self;
-                        BinWrite::write_options(&self.0, writer, options)?;
-                        $(
-                            BinWrite::write_options([<item_ $types>], writer, options)?;
-                        )*
-                    Ok(())
-                }
+    (@impl $(($idx:tt $types:ident))+) => {
+        impl<$($types: BinWrite),+> BinWrite for ($($types,)+) {
+            fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+                $(
+                    BinWrite::write_options(&self.$idx, writer, options)?;
+                )+
+                Ok(())
             }
         }
-
-        binwrite_tuple_impl!($($types),*);
     };
 
-    () => {
-        impl BinWrite for () {
-            fn write_options<W: Write>(&self, _: &mut W, _: &WriterOption) -> Result<()> {
-                Ok(())
-            }
-        }
+    ([$($done:tt)*] $next:tt $($rest:tt)*) => {
+        binwrite_tuple_impl!(@impl $($done)* $next);
+        binwrite_tuple_impl!([$($done)* $next] $($rest)*);
     };
+
+    ([$($done:tt)*]) => {};
 }
 
-binwrite_tuple_impl!(b1, b2, b3, b4, b5, b6, b7, b8, b9, b10, b11, b12, b13, b14, b15, b16, b17, b18, b19, b20);
+impl BinWrite for () {
+    fn write_options<W: Write>(&self, _: &mut W, _: &WriterOption) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Marker fields write nothing, so generic structs don't need `#[binwrite(ignore)]` on them
+impl<T: ?Sized> BinWrite for std::marker::PhantomData<T> {
+    fn write_options<W: Write>(&self, _: &mut W, _: &WriterOption) -> Result<()> {
+        Ok(())
+    }
+}
 
+binwrite_tuple_impl!([] (0 T0) (1 T1) (2 T2) (3 T3) (4 T4) (5 T5) (6 T6) (7 T7) (8 T8) (9 T9) (10 T10) (11 T11) (12 T12) (13 T13) (14 T14) (15 T15) (16 T16) (17 T17) (18 T18) (19 T19) (20 T20) (21 T21) (22 T22) (23 T23) (24 T24) (25 T25) (26 T26) (27 T27) (28 T28) (29 T29) (30 T30) (31 T31));
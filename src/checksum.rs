@@ -0,0 +1,305 @@
+//! # Example:
+//! ```rust
+//! use binwrite::BinWrite;
+//! use binwrite::checksum::{Crc, CrcAppended};
+//!
+//! assert_eq!(Crc::CRC32.checksum(b"123456789"), 0xCBF43926);
+//!
+//! // A format-specific variant, described by its parameters
+//! const MY_CRC: Crc = Crc::new(16, 0x1021, 0xFFFF, false, false, 0);
+//! assert_eq!(MY_CRC.checksum(b"123456789"), 0x29B1);
+//!
+//! let mut bytes = vec![];
+//! CrcAppended::new(&b"123456789"[..], MY_CRC)
+//!     .write_options(&mut bytes, &binwrite::writer_option_new!(endian: binwrite::Endian::Big))
+//!     .unwrap();
+//! assert_eq!(&bytes[9..], &[0x29, 0xB1]);
+//! ```
+use super::*;
+use crate::packet::{fold_ones_complement, ones_complement_sum, PseudoHeader};
+use crate::scratch::ScratchBuffer;
+use std::io::{Error, ErrorKind};
+
+/// A CRC algorithm described by the parameters used in the
+/// [CRC RevEng catalogue](https://reveng.sourceforge.io/crc-catalogue/): register width in bits
+/// (1 to 64), polynomial, initial value, whether input bytes and the output are reflected, and a
+/// value to XOR the output with. A width outside 1 to 64 set directly on the field is clamped
+/// to that range.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Crc {
+    pub width: u8,
+    pub poly: u64,
+    pub init: u64,
+    pub refin: bool,
+    pub refout: bool,
+    pub xorout: u64,
+}
+
+impl Crc {
+    /// CRC-8/SMBUS
+    pub const CRC8: Crc = Crc::new(8, 0x07, 0, false, false, 0);
+    /// CRC-16/ARC, also known as CRC-16/IBM
+    pub const CRC16_ARC: Crc = Crc::new(16, 0x8005, 0, true, true, 0);
+    /// CRC-16/IBM-3740, also known as CRC-16/CCITT-FALSE
+    pub const CRC16_IBM_3740: Crc = Crc::new(16, 0x1021, 0xFFFF, false, false, 0);
+    /// CRC-16/XMODEM
+    pub const CRC16_XMODEM: Crc = Crc::new(16, 0x1021, 0, false, false, 0);
+    /// CRC-32/ISO-HDLC, the CRC-32 used by zip, PNG, Ethernet and BPS patches
+    pub const CRC32: Crc = Crc::new(32, 0x04C1_1DB7, 0xFFFF_FFFF, true, true, 0xFFFF_FFFF);
+    /// CRC-32/BZIP2
+    pub const CRC32_BZIP2: Crc = Crc::new(32, 0x04C1_1DB7, 0xFFFF_FFFF, false, false, 0xFFFF_FFFF);
+    /// CRC-32/ISCSI, also known as CRC-32C (Castagnoli)
+    pub const CRC32C: Crc = Crc::new(32, 0x1EDC_6F41, 0xFFFF_FFFF, true, true, 0xFFFF_FFFF);
+    /// CRC-64/XZ
+    pub const CRC64_XZ: Crc = Crc::new(
+        64, 0x42F0_E1EB_A9EA_3693, 0xFFFF_FFFF_FFFF_FFFF, true, true, 0xFFFF_FFFF_FFFF_FFFF
+    );
+
+    /// Panics (at compile time when used in a const) if `width` is not between 1 and 64
+    pub const fn new(width: u8, poly: u64, init: u64, refin: bool, refout: bool, xorout: u64) -> Self {
+        assert!(width >= 1 && width <= 64, "CRC width must be between 1 and 64 bits");
+        Crc { width, poly, init, refin, refout, xorout }
+    }
+
+    fn width(&self) -> u32 {
+        self.width.clamp(1, 64) as u32
+    }
+
+    fn mask(&self) -> u64 {
+        u64::MAX >> (64 - self.width())
+    }
+
+    /// Start a CRC computation, for checksumming data that is written piece by piece
+    pub fn digest(&self) -> CrcDigest {
+        CrcDigest { crc: *self, register: self.init & self.mask() }
+    }
+
+    /// The CRC of `data`
+    pub fn checksum(&self, data: &[u8]) -> u64 {
+        let mut digest = self.digest();
+        digest.update(data);
+        digest.finish()
+    }
+
+    /// The size of the CRC in bytes when written, the width rounded up to whole bytes
+    pub fn byte_len(&self) -> usize {
+        (self.width() as usize).div_ceil(8)
+    }
+}
+
+/// An in-progress CRC computation, see [Crc::digest]. Implements [Write] so values can be
+/// written directly into it.
+#[derive(Clone, Copy, Debug)]
+pub struct CrcDigest {
+    crc: Crc,
+    register: u64,
+}
+
+impl CrcDigest {
+    pub fn update(&mut self, data: &[u8]) {
+        let width = self.crc.width();
+        let top = 1u64 << (width - 1);
+        let mask = self.crc.mask();
+        for &byte in data {
+            let byte = if self.crc.refin { byte.reverse_bits() } else { byte };
+            for i in (0..8).rev() {
+                let bit = (byte >> i) & 1 != 0;
+                let feedback = (self.register & top != 0) != bit;
+                self.register = (self.register << 1) & mask;
+                if feedback {
+                    self.register ^= self.crc.poly & mask;
+                }
+            }
+        }
+    }
+
+    /// The CRC of all data given to the digest so far
+    pub fn finish(&self) -> u64 {
+        let register = if self.crc.refout {
+            self.register.reverse_bits() >> (64 - self.crc.width())
+        } else {
+            self.register
+        };
+        (register ^ self.crc.xorout) & self.crc.mask()
+    }
+}
+
+impl Write for CrcDigest {
+    fn write(&mut self, data: &[u8]) -> Result<usize> {
+        self.update(data);
+        Ok(data.len())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// A wrapper which writes the inner value followed by the CRC of the bytes it wrote, using the
+/// endianness for byte order and [byte_len](Crc::byte_len) bytes. Use
+/// `#[binwrite(preprocessor(crc_appended(Crc::CRC32)))]` to follow a field with its CRC-32.
+pub struct CrcAppended<'a, T: ?Sized> {
+    value: &'a T,
+    crc: Crc,
+}
+
+impl<'a, T: ?Sized> CrcAppended<'a, T> {
+    pub fn new(value: &'a T, crc: Crc) -> Self {
+        CrcAppended { value, crc }
+    }
+}
+
+impl<'a, T: BinWrite + ?Sized> BinWrite for CrcAppended<'a, T> {
+    fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+        let mut bytes = ScratchBuffer::new(options);
+        BinWrite::write_options(self.value, &mut *bytes, options)?;
+        writer.write_all(&bytes)?;
+
+        let checksum = self.crc.checksum(&bytes);
+        let len = self.crc.byte_len();
+        match options.endian.resolve() {
+            Endian::Big => writer.write_all(&checksum.to_be_bytes()[8 - len..]),
+            _ => writer.write_all(&checksum.to_le_bytes()[..len]),
+        }
+    }
+}
+
+/// A preprocessor for following a value with its CRC. See [CrcAppended].
+pub fn crc_appended<T: BinWrite + ?Sized>(crc: Crc) -> impl Fn(&T) -> CrcAppended<'_, T> {
+    move |value| CrcAppended::new(value, crc)
+}
+
+/// A non-CRC checksum algorithm, for [ChecksumAppended] and [ChecksumFilled]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Checksum {
+    /// Fletcher-16, over bytes
+    Fletcher16,
+    /// Fletcher-32, over little endian 16-bit words (an odd trailing byte is padded with zero)
+    Fletcher32,
+    /// The RFC 1071 internet checksum, optionally covering a UDP/TCP pseudo-header whose length
+    /// is the length of the checksummed data. With a UDP pseudo-header, a checksum of 0 is 0xFFFF
+    /// instead, as 0 means no checksum.
+    Internet(Option<PseudoHeader>),
+}
+
+impl Checksum {
+    /// The checksum of `data`. Returns an error if `data` is too long for the length field of an
+    /// internet checksum's pseudo-header.
+    pub fn checksum(&self, data: &[u8]) -> Result<u64> {
+        match self {
+            Checksum::Fletcher16 => {
+                let (mut sum1, mut sum2) = (0u32, 0u32);
+                for &byte in data {
+                    sum1 = (sum1 + byte as u32) % 255;
+                    sum2 = (sum2 + sum1) % 255;
+                }
+                Ok(((sum2 << 8) | sum1) as u64)
+            }
+            Checksum::Fletcher32 => {
+                let (mut sum1, mut sum2) = (0u64, 0u64);
+                for word in data.chunks(2) {
+                    let word = match word {
+                        [low, high] => u16::from_le_bytes([*low, *high]),
+                        [low] => *low as u16,
+                        _ => unreachable!(),
+                    };
+                    sum1 = (sum1 + word as u64) % 65535;
+                    sum2 = (sum2 + sum1) % 65535;
+                }
+                Ok((sum2 << 16) | sum1)
+            }
+            Checksum::Internet(pseudo_header) => {
+                let sum = match pseudo_header {
+                    Some(pseudo_header) => ones_complement_sum(0, &pseudo_header.to_bytes(data.len())?),
+                    None => 0,
+                };
+                Ok(match !fold_ones_complement(ones_complement_sum(sum, data)) {
+                    0 if pseudo_header.is_some_and(|pseudo_header| pseudo_header.is_udp()) => 0xFFFF,
+                    checksum => checksum as u64,
+                })
+            }
+        }
+    }
+
+    /// The size of the checksum in bytes when written
+    pub fn byte_len(&self) -> usize {
+        match self {
+            Checksum::Fletcher32 => 4,
+            Checksum::Fletcher16 | Checksum::Internet(_) => 2,
+        }
+    }
+
+    fn encode(self, data: &[u8], endian: Endian) -> Result<Vec<u8>> {
+        let checksum = self.checksum(data)?;
+        let len = self.byte_len();
+        Ok(match endian.resolve() {
+            Endian::Big => checksum.to_be_bytes()[8 - len..].to_vec(),
+            _ => checksum.to_le_bytes()[..len].to_vec(),
+        })
+    }
+}
+
+/// A wrapper which writes the inner value followed by its [Checksum], using the endianness for
+/// byte order. Use `#[binwrite(preprocessor(checksum_appended(Checksum::Fletcher16)))]`.
+pub struct ChecksumAppended<'a, T: ?Sized> {
+    value: &'a T,
+    checksum: Checksum,
+}
+
+impl<'a, T: ?Sized> ChecksumAppended<'a, T> {
+    pub fn new(value: &'a T, checksum: Checksum) -> Self {
+        ChecksumAppended { value, checksum }
+    }
+}
+
+impl<'a, T: BinWrite + ?Sized> BinWrite for ChecksumAppended<'a, T> {
+    fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+        let mut bytes = ScratchBuffer::new(options);
+        BinWrite::write_options(self.value, &mut *bytes, options)?;
+        writer.write_all(&bytes)?;
+        writer.write_all(&self.checksum.encode(&bytes, options.endian)?)
+    }
+}
+
+/// A preprocessor for following a value with its checksum. See [ChecksumAppended].
+pub fn checksum_appended<T: BinWrite + ?Sized>(checksum: Checksum) -> impl Fn(&T) -> ChecksumAppended<'_, T> {
+    move |value| ChecksumAppended::new(value, checksum)
+}
+
+/// A wrapper which writes the inner value, then fills in a checksum field at byte offset `at`
+/// within it with the [Checksum] of everything written, as UDP and TCP headers do. The field
+/// should be written as zero. Uses the endianness for byte order, so network protocols should
+/// be written with [Endian::Big]. Use
+/// `#[binwrite(preprocessor(checksum_filled(Checksum::Internet(Some(pseudo_header)), 6)))]` for a
+/// UDP datagram.
+pub struct ChecksumFilled<'a, T: ?Sized> {
+    value: &'a T,
+    checksum: Checksum,
+    at: usize,
+}
+
+impl<'a, T: ?Sized> ChecksumFilled<'a, T> {
+    pub fn new(value: &'a T, checksum: Checksum, at: usize) -> Self {
+        ChecksumFilled { value, checksum, at }
+    }
+}
+
+impl<'a, T: BinWrite + ?Sized> BinWrite for ChecksumFilled<'a, T> {
+    fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+        let mut bytes = ScratchBuffer::new(options);
+        BinWrite::write_options(self.value, &mut *bytes, options)?;
+
+        let checksum = self.checksum.encode(&bytes, options.endian)?;
+        let field = bytes.get_mut(self.at..self.at + checksum.len()).ok_or_else(|| Error::new(
+            ErrorKind::InvalidData,
+            format!("checksum field at {:#x} is past the end of the value", self.at)
+        ))?;
+        field.copy_from_slice(&checksum);
+        writer.write_all(&bytes)
+    }
+}
+
+/// A preprocessor for filling in a checksum field within a value. See [ChecksumFilled].
+pub fn checksum_filled<T: BinWrite + ?Sized>(checksum: Checksum, at: usize) -> impl Fn(&T) -> ChecksumFilled<'_, T> {
+    move |value| ChecksumFilled::new(value, checksum, at)
+}
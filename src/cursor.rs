@@ -0,0 +1,57 @@
+use super::*;
+use std::fmt;
+use std::io::{Cursor, Error, ErrorKind};
+
+/// The error returned by [write_into_cursor] when the value doesn't fit in the rest of the
+/// buffer. It is wrapped in an [io::Error](std::io::Error) with kind
+/// [WriteZero](std::io::ErrorKind::WriteZero), and can be recovered with
+/// [OutputTooSmall::from_io].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OutputTooSmall {
+    /// The number of bytes the value writes
+    pub needed: usize,
+    /// The number of bytes left in the buffer
+    pub available: usize,
+}
+
+impl OutputTooSmall {
+    /// Get the OutputTooSmall inside an io::Error, if that's what it holds
+    pub fn from_io(error: &Error) -> Option<&OutputTooSmall> {
+        error.get_ref()?.downcast_ref()
+    }
+}
+
+impl fmt::Display for OutputTooSmall {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "output buffer too small: needed {} bytes but only {} are available", self.needed, self.available)
+    }
+}
+
+impl std::error::Error for OutputTooSmall {}
+
+/// Write `value` into a fixed buffer at the cursor's position, returning the number of bytes
+/// written. The size is measured first (see [writers::byte_len_of]), so if the value doesn't fit
+/// nothing is written and the error holds an [OutputTooSmall] saying how much space is needed.
+///
+/// ```rust
+/// use std::io::Cursor;
+/// use binwrite::{write_into_cursor, OutputTooSmall};
+///
+/// let mut buf = [0u8; 4];
+/// let mut cursor = Cursor::new(&mut buf[..]);
+/// assert_eq!(write_into_cursor(&1u16, &mut cursor, &Default::default()).unwrap(), 2);
+///
+/// let error = write_into_cursor(&1u32, &mut cursor, &Default::default()).unwrap_err();
+/// assert_eq!(OutputTooSmall::from_io(&error), Some(&OutputTooSmall { needed: 4, available: 2 }));
+/// ```
+pub fn write_into_cursor<T>(value: &T, cursor: &mut Cursor<&mut [u8]>, options: &WriterOption) -> Result<usize>
+    where T: BinWrite + ?Sized,
+{
+    let needed: usize = writers::byte_len_of(value, options)?;
+    let available = cursor.get_ref().len().saturating_sub(cursor.position() as usize);
+    if needed > available {
+        return Err(Error::new(ErrorKind::WriteZero, OutputTooSmall { needed, available }));
+    }
+    BinWrite::write_options(value, cursor, options)?;
+    Ok(needed)
+}
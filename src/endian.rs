@@ -0,0 +1,53 @@
+use super::*;
+
+/// Internal macro for defining newtypes which always write with a specific endianness
+macro_rules! explicit_endian_types {
+    ($($le:ident, $be:ident => $type_name:ty),*$(,)?) => {
+        $(
+            explicit_endian_types!(@type $le, $type_name, to_le_bytes, "little");
+            explicit_endian_types!(@type $be, $type_name, to_be_bytes, "big");
+        )*
+    };
+
+    (@type $name:ident, $type_name:ty, $to_bytes:ident, $endian:literal) => {
+        #[doc = concat!("A `", stringify!($type_name), "` which is always written as ", $endian,
+                        " endian, regardless of the endianness it is written with.")]
+        #[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+        pub struct $name(pub $type_name);
+
+        impl BinWrite for $name {
+            fn write_options<W: Write>(&self, writer: &mut W, _options: &WriterOption) -> Result<()> {
+                writer.write_all(&self.0.$to_bytes())
+            }
+        }
+
+        impl FixedSize for $name {
+            const SIZE: usize = std::mem::size_of::<$type_name>();
+        }
+
+        impl From<$type_name> for $name {
+            fn from(value: $type_name) -> Self {
+                $name(value)
+            }
+        }
+
+        impl From<$name> for $type_name {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+    };
+}
+
+explicit_endian_types!(
+    U16Le, U16Be => u16,
+    U32Le, U32Be => u32,
+    U64Le, U64Be => u64,
+    U128Le, U128Be => u128,
+    I16Le, I16Be => i16,
+    I32Le, I32Be => i32,
+    I64Le, I64Be => i64,
+    I128Le, I128Be => i128,
+    F32Le, F32Be => f32,
+    F64Le, F64Be => f64,
+);
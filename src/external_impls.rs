@@ -0,0 +1,92 @@
+//! BinWrite implementations for types from other crates, each behind a feature of the same name
+//! as the crate.
+#[allow(unused_imports)]
+use super::*;
+
+#[cfg(feature = "smallvec")]
+impl<A> BinWrite for smallvec::SmallVec<A>
+    where A: smallvec::Array,
+          A::Item: BinWrite,
+{
+    fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+        BinWrite::write_options(&self[..], writer, options)
+    }
+}
+
+#[cfg(feature = "arrayvec")]
+impl<B: BinWrite, const CAP: usize> BinWrite for arrayvec::ArrayVec<B, CAP> {
+    fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+        BinWrite::write_options(&self[..], writer, options)
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl<B: BinWrite, const N: usize> BinWrite for heapless::Vec<B, N> {
+    fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+        BinWrite::write_options(&self[..], writer, options)
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl BinWrite for bytes::Bytes {
+    fn write_options<W: Write>(&self, writer: &mut W, _options: &WriterOption) -> Result<()> {
+        writer.write_all(self)
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl BinWrite for bytes::BytesMut {
+    fn write_options<W: Write>(&self, writer: &mut W, _options: &WriterOption) -> Result<()> {
+        writer.write_all(self)
+    }
+}
+
+/// Internal macro for implementing BinWrite for mint types, writing each component in order
+#[cfg(feature = "mint")]
+macro_rules! binwrite_mint_impl {
+    ($($type_name:ident { $($field:ident),* }),*$(,)?) => {
+        $(
+            impl<T: BinWrite> BinWrite for mint::$type_name<T> {
+                fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+                    $(
+                        BinWrite::write_options(&self.$field, writer, options)?;
+                    )*
+                    Ok(())
+                }
+            }
+        )*
+    }
+}
+
+#[cfg(feature = "mint")]
+binwrite_mint_impl!(
+    Vector2 { x, y },
+    Vector3 { x, y, z },
+    Vector4 { x, y, z, w },
+    Point2 { x, y },
+    Point3 { x, y, z },
+    Quaternion { v, s },
+    ColumnMatrix2 { x, y },
+    ColumnMatrix3 { x, y, z },
+    ColumnMatrix4 { x, y, z, w },
+    RowMatrix2 { x, y },
+    RowMatrix3 { x, y, z },
+    RowMatrix4 { x, y, z, w },
+);
+
+/// Vectors and matrices are written component by component in nalgebra's storage order, which is
+/// column-major.
+#[cfg(feature = "nalgebra")]
+impl<T, R, C, S> BinWrite for nalgebra::Matrix<T, R, C, S>
+    where T: nalgebra::Scalar + BinWrite,
+          R: nalgebra::Dim,
+          C: nalgebra::Dim,
+          S: nalgebra::RawStorage<T, R, C>,
+{
+    fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+        for component in self.iter() {
+            BinWrite::write_options(component, writer, options)?;
+        }
+        Ok(())
+    }
+}
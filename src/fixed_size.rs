@@ -0,0 +1,99 @@
+/// A trait for types which always write the same number of bytes, regardless of value or
+/// options. The size is an associated constant, so it can be used to allocate and validate
+/// buffers without hardcoding numbers.
+///
+/// ```rust
+/// use binwrite::{BinWrite, FixedSize};
+///
+/// type Header = ([u8; 4], u32, (u16, u16));
+///
+/// let header: Header = (*b"TEST", 1, (2, 3));
+/// let mut buffer = [0u8; <Header as FixedSize>::SIZE];
+///
+/// header.write(&mut &mut buffer[..]).unwrap();
+/// assert_eq!(<Header as FixedSize>::SIZE, 12);
+/// ```
+pub trait FixedSize {
+    const SIZE: usize;
+}
+
+/// Assert at compile time that a [FixedSize] type writes exactly the given number of bytes, for
+/// checking layouts against the sizes in C headers. Compilation fails if the sizes differ.
+///
+/// ```rust
+/// use binwrite::assert_fixed_size;
+///
+/// type Header = ([u8; 4], u32, (u16, u16));
+///
+/// assert_fixed_size!(Header, 12);
+/// assert_fixed_size!(u64, 8);
+/// ```
+#[macro_export] macro_rules! assert_fixed_size {
+    ($type_name:ty, $size:expr $(,)?) => {
+        const _: () = assert!(
+            <$type_name as $crate::FixedSize>::SIZE == $size,
+            concat!("size of ", stringify!($type_name), " is not ", stringify!($size))
+        );
+    }
+}
+
+/// Internal macro for implementing FixedSize for primitives
+macro_rules! fixed_size_impl {
+    ($($type_name:ty),*$(,)?) => {
+        $(
+            impl FixedSize for $type_name {
+                const SIZE: usize = std::mem::size_of::<$type_name>();
+            }
+        )*
+    }
+}
+
+fixed_size_impl!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64);
+
+macro_rules! fixed_size_array_impl {
+    ($($size:literal),*$(,)?) => {
+        $(
+            // Each item is padded to its alignment when written, see BinWrite::ALIGNMENT
+            impl<B: FixedSize + crate::BinWrite> FixedSize for [B; $size] {
+                const SIZE: usize = B::SIZE.div_ceil(B::ALIGNMENT) * B::ALIGNMENT * $size;
+            }
+        )*
+    }
+}
+
+fixed_size_array_impl!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20);
+
+/// Internal macro to implement FixedSize for every size tuple 1 to 32, in the same manner as the
+/// BinWrite tuple implementations
+macro_rules! fixed_size_tuple_impl {
+    (@impl $($types:ident)+) => {
+        impl<$($types: FixedSize),+> FixedSize for ($($types,)+) {
+            const SIZE: usize = 0 $(+ $types::SIZE)+;
+        }
+    };
+
+    ([$($done:ident)*] $next:ident $($rest:ident)*) => {
+        fixed_size_tuple_impl!(@impl $($done)* $next);
+        fixed_size_tuple_impl!([$($done)* $next] $($rest)*);
+    };
+
+    ([$($done:ident)*]) => {};
+}
+
+impl FixedSize for () {
+    const SIZE: usize = 0;
+}
+
+impl<T: ?Sized> FixedSize for std::marker::PhantomData<T> {
+    const SIZE: usize = 0;
+}
+
+fixed_size_tuple_impl!([] T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11 T12 T13 T14 T15 T16 T17 T18 T19 T20 T21 T22 T23 T24 T25 T26 T27 T28 T29 T30 T31);
+
+impl<B: FixedSize + ?Sized> FixedSize for &B {
+    const SIZE: usize = B::SIZE;
+}
+
+impl<B: FixedSize + ?Sized> FixedSize for &mut B {
+    const SIZE: usize = B::SIZE;
+}
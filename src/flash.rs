@@ -0,0 +1,84 @@
+use super::*;
+use std::io::{Error, ErrorKind};
+use std::ops::Range;
+
+/// A fixed-size image (such as a flash or ROM image) which values are placed into at explicit
+/// offsets, with any gaps left as the fill byte (0xFF by default, matching erased flash).
+/// Placing a value that would run past the end of the image or overlap an earlier value is an
+/// error.
+///
+/// ```rust
+/// use binwrite::{FlashImage, WriterOption};
+///
+/// let options = WriterOption::default();
+/// let mut image = FlashImage::new(0x10);
+/// image.place_at(0x4, &0x12345678u32, &options).unwrap();
+/// image.place_at(0xC, &[1u8, 2], &options).unwrap();
+/// assert!(image.place_at(0x6, &0u32, &options).is_err());
+///
+/// assert_eq!(image.as_bytes(), &[
+///     0xFF, 0xFF, 0xFF, 0xFF, 0x78, 0x56, 0x34, 0x12,
+///     0xFF, 0xFF, 0xFF, 0xFF, 1, 2, 0xFF, 0xFF,
+/// ]);
+/// ```
+pub struct FlashImage {
+    data: Vec<u8>,
+    placed: Vec<Range<usize>>,
+}
+
+impl FlashImage {
+    /// Create an image of `size` bytes filled with 0xFF
+    pub fn new(size: usize) -> Self {
+        Self::with_fill(size, 0xFF)
+    }
+
+    /// Create an image of `size` bytes filled with `fill`
+    pub fn with_fill(size: usize, fill: u8) -> Self {
+        FlashImage { data: vec![fill; size], placed: Vec::new() }
+    }
+
+    /// Write `value` into the image at `offset`
+    pub fn place_at<T: BinWrite + ?Sized>(&mut self, offset: usize, value: &T, options: &WriterOption) -> Result<()> {
+        let mut bytes = vec![];
+        BinWrite::write_options(value, &mut bytes, options)?;
+
+        let end = offset.checked_add(bytes.len()).filter(|&end| end <= self.data.len()).ok_or_else(|| Error::new(
+            ErrorKind::InvalidData,
+            format!("{:#x} bytes at {:#x} is past the end of a {:#x} byte image", bytes.len(), offset, self.data.len())
+        ))?;
+        let range = offset..end;
+        if let Some(existing) = self.placed.iter().find(|existing| existing.start < range.end && range.start < existing.end) {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("{:#x}..{:#x} overlaps {:#x}..{:#x}", range.start, range.end, existing.start, existing.end)
+            ));
+        }
+
+        self.data[range.clone()].copy_from_slice(&bytes);
+        self.placed.push(range);
+        Ok(())
+    }
+
+    /// The size of the image in bytes
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.data
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.data
+    }
+}
+
+impl BinWrite for FlashImage {
+    fn write_options<W: Write>(&self, writer: &mut W, _options: &WriterOption) -> Result<()> {
+        writer.write_all(&self.data)
+    }
+}
@@ -0,0 +1,151 @@
+use super::*;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::io::{Error, ErrorKind};
+
+enum Target {
+    /// A label, resolved by this fragment or the one it is spliced into
+    Label(String),
+    /// An offset from the start of this fragment
+    Resolved(u64),
+}
+
+struct Fixup {
+    at: usize,
+    target: Target,
+    encode: fn(u64, &WriterOption) -> Result<Vec<u8>>,
+}
+
+fn encode_offset<P: TryFrom<u64> + BinWrite>(offset: u64, options: &WriterOption) -> Result<Vec<u8>> {
+    let offset = P::try_from(offset)
+        .map_err(|_| Error::new(ErrorKind::InvalidData, format!("offset {:#x} does not fit in offset type", offset)))?;
+    let mut bytes = vec![];
+    offset.write_options(&mut bytes, options)?;
+    Ok(bytes)
+}
+
+/// A detached, position-independent output buffer with its own label namespace, for assembling
+/// large files from sub-structures serialized independently (for example on separate threads).
+/// Offsets to labels are written as placeholders by [offset_to](Fragment::offset_to) and
+/// resolved when the fragment is [spliced](Fragment::splice) into a parent or
+/// [finished](Fragment::finish) at its final position.
+///
+/// Labels are private to the fragment they were defined in. When a fragment is spliced, offsets
+/// to its own labels are relocated by where it landed in the parent, and offsets to labels it
+/// doesn't define are resolved by the parent instead, so a sub-structure can point at shared
+/// tables in the file it ends up in.
+///
+/// ```rust
+/// use binwrite::{BinWrite, Fragment};
+///
+/// let options = binwrite::writer_option_new!(endian: binwrite::Endian::Big);
+///
+/// let mut entry = Fragment::new(&options);
+/// entry.offset_to::<u8, _>("name");
+/// entry.offset_to::<u8, _>("strings");
+/// entry.label("name").unwrap();
+/// b"entry".write(&mut entry).unwrap();
+///
+/// let mut file = Fragment::new(&options);
+/// 0xFFu8.write(&mut file).unwrap();
+/// file.splice(entry);
+/// file.label("strings").unwrap();
+///
+/// assert_eq!(file.finish(0x10).unwrap(), b"\xff\x13\x18entry");
+/// ```
+pub struct Fragment {
+    bytes: Vec<u8>,
+    labels: HashMap<String, usize>,
+    fixups: Vec<Fixup>,
+    options: WriterOption,
+}
+
+impl Fragment {
+    /// Create an empty fragment. Offsets are written using `options`.
+    pub fn new(options: &WriterOption) -> Self {
+        Fragment { bytes: vec![], labels: HashMap::new(), fixups: vec![], options: options.clone() }
+    }
+
+    /// The number of bytes written so far
+    pub fn pos(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Define a label at the current position. Labels must be unique within a fragment.
+    pub fn label<S: Into<String>>(&mut self, name: S) -> Result<()> {
+        let name = name.into();
+        if self.labels.contains_key(&name) {
+            return Err(Error::new(ErrorKind::InvalidInput, format!("label {:?} is already defined", name)));
+        }
+        self.labels.insert(name, self.bytes.len());
+        Ok(())
+    }
+
+    /// Write a placeholder for the absolute offset of `label` as a `P`, filled in once the
+    /// fragment's final position is known. The label may be defined before or after this call.
+    pub fn offset_to<P, S>(&mut self, label: S)
+        where P: TryFrom<u64> + BinWrite + FixedSize,
+              S: Into<String>,
+    {
+        self.fixups.push(Fixup {
+            at: self.bytes.len(),
+            target: Target::Label(label.into()),
+            encode: encode_offset::<P>,
+        });
+        self.bytes.resize(self.bytes.len() + P::SIZE, 0);
+    }
+
+    /// Append `child` to this fragment, relocating its offsets by where it lands. The child's
+    /// labels are not visible to this fragment.
+    pub fn splice(&mut self, child: Fragment) {
+        let base = self.bytes.len();
+        for fixup in child.fixups {
+            let target = match fixup.target {
+                Target::Label(label) => match child.labels.get(&label) {
+                    Some(&pos) => Target::Resolved((base + pos) as u64),
+                    None => Target::Label(label),
+                },
+                Target::Resolved(offset) => Target::Resolved(base as u64 + offset),
+            };
+            self.fixups.push(Fixup { at: base + fixup.at, target, encode: fixup.encode });
+        }
+        self.bytes.extend_from_slice(&child.bytes);
+    }
+
+    /// The position of every offset in the fragment when it is written at `base`, in order, for
+    /// emitting a relocation table for loaders which relocate the output at runtime or for tools
+    /// which patch it afterwards. Includes the offsets of spliced fragments.
+    pub fn relocations(&self, base: u64) -> Vec<u64> {
+        let mut relocations: Vec<u64> = self.fixups.iter().map(|fixup| base + fixup.at as u64).collect();
+        relocations.sort_unstable();
+        relocations
+    }
+
+    /// Resolve every offset for the fragment being written at `base` in the output, returning the
+    /// finished bytes. Returns an error if an offset refers to a label which was never defined.
+    pub fn finish(mut self, base: u64) -> Result<Vec<u8>> {
+        for fixup in &self.fixups {
+            let offset = match &fixup.target {
+                Target::Label(label) => match self.labels.get(label) {
+                    Some(&pos) => pos as u64,
+                    None => return Err(Error::new(ErrorKind::NotFound, format!("label {:?} is not defined", label))),
+                },
+                Target::Resolved(offset) => *offset,
+            };
+            let bytes = (fixup.encode)(base + offset, &self.options)?;
+            self.bytes[fixup.at..fixup.at + bytes.len()].copy_from_slice(&bytes);
+        }
+        Ok(self.bytes)
+    }
+}
+
+impl Write for Fragment {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.bytes.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,115 @@
+//! # Example:
+//! ```rust
+//! use std::io::Result;
+//! use binwrite::{Endian, WriterOption};
+//! use binwrite::layout::{DescribeLayout, Layout, LayoutRecorder};
+//!
+//! struct Header {
+//!     magic: [u8; 4],
+//!     size: u32,
+//! }
+//!
+//! impl DescribeLayout for Header {
+//!     fn describe_layout(&self, options: &WriterOption) -> Result<Layout> {
+//!         let mut recorder = LayoutRecorder::new(std::io::sink());
+//!         recorder.field("magic", &self.magic, options)?;
+//!         recorder.field("size", &self.size, options)?;
+//!         Ok(recorder.into_inner().1)
+//!     }
+//! }
+//!
+//! let options = binwrite::writer_option_new!(endian: Endian::Big);
+//! let layout = Header { magic: *b"HEAD", size: 8 }.describe_layout(&options).unwrap();
+//! assert_eq!(layout.fields[1].offset, 4);
+//! assert_eq!(
+//!     layout.to_string(),
+//!     "offset  size  endian  field\n\
+//!      0x0     0x4   Big     magic\n\
+//!      0x4     0x4   Big     size\n"
+//! );
+//! ```
+use super::*;
+use std::fmt;
+
+/// Where a single field landed in the output
+#[derive(Clone, Debug)]
+pub struct FieldLayout {
+    pub name: String,
+    pub offset: usize,
+    pub size: usize,
+    pub endian: Endian,
+}
+
+/// The layout of every field recorded by a [LayoutRecorder], displayed as a table
+#[derive(Clone, Debug, Default)]
+pub struct Layout {
+    pub fields: Vec<FieldLayout>,
+}
+
+impl fmt::Display for Layout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{:<8}{:<6}{:<8}field", "offset", "size", "endian")?;
+        for field in &self.fields {
+            let endian: String = (&field.endian).into();
+            writeln!(
+                f, "{:<8}{:<6}{:<8}{}",
+                format!("{:#x}", field.offset), format!("{:#x}", field.size), endian, field.name
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// A trait for types which can describe where each of their fields is written, for printing in
+/// tests and debuggers. See [LayoutRecorder].
+pub trait DescribeLayout {
+    fn describe_layout(&self, options: &WriterOption) -> Result<Layout>;
+}
+
+/// A writer which records the offset, size and endianness of each field written with
+/// [field](LayoutRecorder::field). Offsets are relative to the creation of the recorder.
+pub struct LayoutRecorder<W: Write> {
+    inner: W,
+    pos: usize,
+    layout: Layout,
+}
+
+impl<W: Write> LayoutRecorder<W> {
+    pub fn new(inner: W) -> Self {
+        LayoutRecorder { inner, pos: 0, layout: Layout::default() }
+    }
+
+    /// Write `value`, recording where it landed under `name`
+    pub fn field<T: BinWrite + ?Sized>(&mut self, name: &str, value: &T, options: &WriterOption) -> Result<()> {
+        let offset = self.pos;
+        BinWrite::write_options(value, self, options)?;
+        self.layout.fields.push(FieldLayout {
+            name: name.to_owned(),
+            offset,
+            size: self.pos - offset,
+            endian: options.endian,
+        });
+        Ok(())
+    }
+
+    pub fn layout(&self) -> &Layout {
+        &self.layout
+    }
+
+    /// Unwrap the LayoutRecorder, returning the inner writer and the recorded layout
+    pub fn into_inner(self) -> (W, Layout) {
+        (self.inner, self.layout)
+    }
+}
+
+impl<W: Write> Write for LayoutRecorder<W> {
+    fn write(&mut self, data: &[u8]) -> Result<usize> {
+        let amount = self.inner.write(data)?;
+        self.pos += amount;
+        Ok(amount)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.inner.flush()
+    }
+}
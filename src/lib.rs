@@ -36,13 +36,52 @@ use std::io::{Result, Write};
 /// Derive macro for BinWrite. [Usage here](BinWrite).
 pub use binwrite_derive::BinWrite;
 
+/// Module for [Crc](checksum::Crc), for computing and writing checksums
+pub mod checksum;
+/// Integer and float types with a fixed endianness (example: [U32Be](endian::U32Be))
+pub mod endian;
+/// Module for [LayoutRecorder\<W\>](layout::LayoutRecorder), for recording where each field is written
+pub mod layout;
+/// Module for [PacketBuilder](packet::PacketBuilder), for building layered network packets
+pub mod packet;
+/// Module for [assert_writes](testing::assert_writes) and other helpers, for fuzzing and property
+/// testing BinWrite implementations
+#[cfg(any(feature = "arbitrary", feature = "proptest"))]
+pub mod testing;
+/// Module for [RecordStream\<W\>](record::RecordStream) and [Journal\<F\>](record::Journal), for
+/// writing framed records
+pub mod record;
+/// Module for [StatsWriter\<W\>](stats::StatsWriter), for gathering statistics about a write
+pub mod stats;
 /// Module for [WriteTrack\<T\>](write_track::WriteTrack)
 pub mod write_track;
 /// Built-in special writers (example: C strings)
 pub mod writers;
 mod binwrite_impls;
+mod cursor;
+mod external_impls;
+mod fixed_size;
+mod flash;
+mod fragment;
+mod limit;
+mod manual_impl;
+mod multi_stream;
+mod patcher;
+mod profile;
+mod scratch;
+mod snapshot;
 
 pub use binwrite_impls::*;
+pub use cursor::{write_into_cursor, OutputTooSmall};
+pub use fixed_size::FixedSize;
+pub use flash::FlashImage;
+pub use fragment::Fragment;
+pub use limit::LimitedWriter;
+pub use multi_stream::{BinWriteMulti, MultiStream};
+pub use patcher::Patcher;
+pub use profile::Profile;
+pub use scratch::WriteScratch;
+pub use snapshot::{SnapshotBuffer, SnapshotDiff};
 
 /// A trait providing the ability to write the struct to a writer
 ///
@@ -94,6 +133,10 @@ pub use binwrite_impls::*;
 /// }
 /// ```
 ///
+/// Endianness only affects types with multi-byte values, such as integers, floats and anything
+/// built from them. `u8`, `i8`, `char` (written as UTF-8), `str`, `String` and byte arrays or
+/// slices are written the same regardless, so `big`/`little` on those fields has no effect.
+///
 /// ### Using a preprocessor
 /// ```rust
 /// use binwrite::BinWrite;
@@ -221,6 +264,61 @@ pub use binwrite_impls::*;
 /// ```
 /// use `pad` and `pad_after` for fixed amounts of padding.
 ///
+/// ### Attribute order
+/// When several attributes are used on one field, they apply in this order:
+/// 1. `pad` and `align` write padding before the field
+/// 2. `preprocessor` converts the field
+/// 3. The (converted) value is written using `with` if given, otherwise using its BinWrite impl
+/// 4. `postprocessor` receives the bytes from step 3 and its result is written instead
+/// 5. `pad_after` and `align_after` write padding after the field
+///
+/// Alignment is relative to the start of the struct and counts the bytes actually written, so
+/// postprocessors that change the size are accounted for.
+///
+/// ### Packed structs
+/// Fields of a `#[repr(packed)]` struct may be unaligned, so they can't be borrowed. To write one,
+/// copy the fields out first (`Copy` field types only) and write the copies.
+/// ```rust
+/// use std::io::{Result, Write};
+/// use binwrite::{BinWrite, WriterOption};
+///
+/// #[repr(C, packed)]
+/// struct Header {
+///     kind: u8,
+///     offset: u32,
+/// }
+///
+/// impl BinWrite for Header {
+///     fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+///         let (kind, offset) = (self.kind, self.offset);
+///         (kind, offset).write_options(writer, options)
+///     }
+/// }
+///
+/// fn main() {
+///     let mut bytes = vec![];
+///
+///     Header { kind: 1, offset: 2 }.write(&mut bytes).unwrap();
+///
+///     assert_eq!(bytes.len(), std::mem::size_of::<Header>());
+/// }
+/// ```
+///
+/// ### Large tuples
+/// Tuples of up to 32 items implement BinWrite. For anything wider, nest tuples, as a nested
+/// tuple writes the same bytes as the equivalent flat tuple.
+/// ```rust
+/// use binwrite::BinWrite;
+///
+/// let mut flat = vec![];
+/// let mut nested = vec![];
+///
+/// (1u8, 2u8, 3u8, 4u8).write(&mut flat).unwrap();
+/// ((1u8, 2u8), (3u8, 4u8)).write(&mut nested).unwrap();
+///
+/// assert_eq!(flat, nested);
+/// ```
+///
 /// ### Advanced Preprocessors
 /// Using generics/closures, you can make "configurable" and more reusable preprocessors.
 ///
@@ -252,6 +350,26 @@ pub use binwrite_impls::*;
 /// }
 /// ```
 ///
+/// ### Borrowing in preprocessors
+/// Preprocessors receive the field by reference, and their output can borrow from it, so large
+/// fields don't need to be cloned just to add to them. References, tuples, `Cow`, `Box`, `Rc` and
+/// `Arc` all implement BinWrite.
+///
+/// Example (prepending a length without copying the data):
+/// ```rust
+/// use binwrite::BinWrite;
+///
+/// fn with_len(data: &Vec<u8>) -> (u32, &Vec<u8>) {
+///     (data.len() as u32, data)
+/// }
+///
+/// let data = vec![1u8, 2, 3];
+/// let mut bytes = vec![];
+/// with_len(&data).write_options(&mut bytes, &binwrite::writer_option_new!(endian: binwrite::Endian::Big)).unwrap();
+///
+/// assert_eq!(bytes, vec![0, 0, 0, 3, 1, 2, 3]);
+/// ```
+///
 /// ### Postprocessors
 ///
 /// Postprocessors are functions which take a `Vec<u8>` (what would normally be written) and
@@ -288,18 +406,57 @@ pub use binwrite_impls::*;
 /// }
 /// ```
 pub trait BinWrite {
+    /// The alignment of the type. When greater than 1, each item of a `Vec`, slice or array of
+    /// this type is padded to a multiple of it so that every item stays aligned, as in a C array.
+    /// Padding is relative to the start of the collection, so the collection itself should be
+    /// aligned (see [writers::align_after]).
+    const ALIGNMENT: usize = 1;
+
     fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
         self.write_options(writer, &WriterOption::default())
     }
 
     fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()>;
+
+    /// Write a slice of this type, as done by the `Vec`, slice and array implementations. By
+    /// default each item is written in turn, padded to [ALIGNMENT](BinWrite::ALIGNMENT). `u8`
+    /// overrides this to write the whole slice with a single `write_all`.
+    fn write_slice<W: Write>(items: &[Self], writer: &mut W, options: &WriterOption) -> Result<()>
+        where Self: Sized,
+    {
+        writers::write_items(items, writer, options)
+    }
+
+    /// Write with the given options, taking temporary buffers from `scratch` so that repeated
+    /// writes don't allocate them again (see [WriteScratch]). If `options.scratch` is already
+    /// `scratch`, the options are passed through as-is; otherwise they are cloned once with it set.
+    fn write_options_reuse<W: Write>(&self, writer: &mut W, options: &WriterOption, scratch: &std::sync::Arc<WriteScratch>) -> Result<()> {
+        match &options.scratch {
+            Some(current) if std::sync::Arc::ptr_eq(current, scratch) => self.write_options(writer, options),
+            _ => {
+                let options = WriterOption { scratch: Some(scratch.clone()), ..options.clone() };
+                self.write_options(writer, &options)
+            }
+        }
+    }
+
+    /// Write with the given options, enforcing [max_size](WriterOption::max_size) if it is set.
+    /// Going over the limit is an error, and nothing past the limit reaches the writer.
+    fn write_limited<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+        match options.max_size {
+            Some(max_size) => self.write_options(&mut LimitedWriter::new(writer, max_size), options),
+            None => self.write_options(writer, options),
+        }
+    }
 }
 
 /// An enum to represent what endianness to write with
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Endian {
     Big,
     Little,
+    /// The endianness of the target. Writing multi-byte values with this is an error when
+    /// [deny_native](WriterOption::deny_native) is set.
     Native,
 }
 
@@ -320,6 +477,26 @@ impl Into<String> for &Endian {
 #[derive(Default, Clone)]
 pub struct WriterOption {
     pub endian: Endian,
+    /// XOR mask key used by [writers::masked] and [writers::mask_key]
+    pub mask: Option<[u8; 4]>,
+    /// Encryption key used by [writers::encrypted] and [writers::RecordEncryptor]
+    pub key: Option<Vec<u8>>,
+    /// Maximum total number of bytes written by [BinWrite::write_limited]
+    pub max_size: Option<u64>,
+    /// Buffers reused by writers which write to memory first. See [WriteScratch].
+    pub scratch: Option<std::sync::Arc<WriteScratch>>,
+    /// Make output reproducible by writing unordered collections (`HashMap`, `HashSet`) in a
+    /// stable order. Combine with [timestamp](WriterOption::timestamp) for byte-identical
+    /// archives across machines and runs.
+    pub canonical: bool,
+    /// Fixed Unix timestamp written in place of modification times by
+    /// [TarHeader](writers::tar::TarHeader) and [ZIP headers](writers::zip::LocalFileHeader)
+    pub timestamp: Option<u64>,
+    /// Encoder used by [writers::PayloadEncoded]
+    pub payload_encoder: Option<std::sync::Arc<std::sync::Mutex<dyn writers::PayloadEncoder>>>,
+    /// Return an error when writing a multi-byte value with [Endian::Native], to catch files
+    /// accidentally written in host byte order
+    pub deny_native: bool,
     /// A private field to prevent users from creating/destructuring in a non-forwards compatible
     /// manner
     _prevent_creation: ()
@@ -339,6 +516,18 @@ pub struct WriterOption {
     }
 }
 
+impl Endian {
+    /// The endianness actually written, with [Native](Endian::Native) resolved to
+    /// [Big](Endian::Big) or [Little](Endian::Little) for the target
+    pub fn resolve(self) -> Endian {
+        match self {
+            Endian::Native if cfg!(target_endian = "little") => Endian::Little,
+            Endian::Native => Endian::Big,
+            endian => endian,
+        }
+    }
+}
+
 impl Default for Endian {
     fn default() -> Endian {
         Endian::Native
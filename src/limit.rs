@@ -0,0 +1,52 @@
+use std::io::{Error, ErrorKind, Result, Write};
+
+/// A writer which fails once more than a maximum number of bytes would be written through it.
+/// A write that would go over the limit writes nothing and returns an error, so the output never
+/// exceeds the limit. Used by [BinWrite::write_limited](crate::BinWrite::write_limited) when
+/// [WriterOption::max_size](crate::WriterOption::max_size) is set.
+pub struct LimitedWriter<W: Write> {
+    inner: W,
+    written: u64,
+    max_size: u64,
+}
+
+impl<W: Write> LimitedWriter<W> {
+    pub fn new(inner: W, max_size: u64) -> Self {
+        LimitedWriter { inner, written: 0, max_size }
+    }
+
+    /// The number of bytes written so far
+    pub fn written(&self) -> u64 {
+        self.written
+    }
+
+    /// The number of bytes which can still be written
+    pub fn remaining(&self) -> u64 {
+        self.max_size - self.written
+    }
+
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: Write> Write for LimitedWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        if buf.len() as u64 > self.remaining() {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "output exceeds the maximum size of {} bytes ({} written, {} more requested)",
+                    self.max_size, self.written, buf.len()
+                )
+            ));
+        }
+        let written = self.inner.write(buf)?;
+        self.written += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.inner.flush()
+    }
+}
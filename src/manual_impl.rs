@@ -0,0 +1,114 @@
+/// Implement BinWrite for a struct by listing what to write in order, for types which need a
+/// manual impl but are mostly plain fields. The optional `endian` overrides the endianness for
+/// the whole struct. Each item is one of:
+/// * `field` - write `self.field` using its BinWrite impl
+/// * `with(writer) field` - write `self.field` using a custom writer function
+/// * `pad(amount)` - write `amount` bytes of zero padding
+/// * `align(alignment)` - pad until the position, relative to the start of the struct, is a
+///   multiple of `alignment`
+/// * `trim_default(a, b, ...)` - write the listed fields, leaving off any trailing fields equal
+///   to their `Default` value (for optional trailing fields detected by record length)
+///
+/// With `fn name` in place of the endian, an inherent method `name(&self, writer, options)` is
+/// generated instead of a BinWrite impl, for formats where groups of fields (such as a header and
+/// a body) are written at different times. Alignment in a group is relative to the start of that
+/// group.
+///
+/// Amounts and alignments are expressions evaluated when writing. They can't refer to `self`, so
+/// padding computed from other fields still needs a fully manual impl.
+///
+/// ```rust
+/// use binwrite::{binwrite_manual_impl, BinWrite, writers::utf16_null_string};
+///
+/// struct Header {
+///     magic: [u8; 2],
+///     name: String,
+///     count: u32,
+/// }
+///
+/// binwrite_manual_impl!(Header, endian = Little, {
+///     magic,
+///     with(utf16_null_string) name,
+///     align(4),
+///     count,
+/// });
+///
+/// let mut bytes = vec![];
+/// Header { magic: *b"HD", name: String::from("a"), count: 1 }.write(&mut bytes).unwrap();
+///
+/// assert_eq!(bytes, b"HDa\0\0\0\0\0\x01\0\0\0");
+/// ```
+#[macro_export] macro_rules! binwrite_manual_impl {
+    ($type_name:ty, endian = $endian:ident, { $($items:tt)* }) => {
+        impl $crate::BinWrite for $type_name {
+            fn write_options<W: std::io::Write>(&self, writer: &mut W, options: &$crate::WriterOption) -> std::io::Result<()> {
+                let mut options = options.clone();
+                options.endian = $crate::Endian::$endian;
+                let mut writer = $crate::write_track::WriteTrack::new(writer);
+                $crate::binwrite_manual_impl!(@items self, writer, options, $($items)*);
+                Ok(())
+            }
+        }
+    };
+
+    ($type_name:ty, { $($items:tt)* }) => {
+        impl $crate::BinWrite for $type_name {
+            fn write_options<W: std::io::Write>(&self, writer: &mut W, options: &$crate::WriterOption) -> std::io::Result<()> {
+                let mut writer = $crate::write_track::WriteTrack::new(writer);
+                $crate::binwrite_manual_impl!(@items self, writer, options, $($items)*);
+                Ok(())
+            }
+        }
+    };
+
+    ($type_name:ty, fn $name:ident, { $($items:tt)* }) => {
+        impl $type_name {
+            pub fn $name<W: std::io::Write>(&self, writer: &mut W, options: &$crate::WriterOption) -> std::io::Result<()> {
+                let mut writer = $crate::write_track::WriteTrack::new(writer);
+                $crate::binwrite_manual_impl!(@items self, writer, options, $($items)*);
+                Ok(())
+            }
+        }
+    };
+
+    (@items $self:ident, $writer:ident, $options:ident, pad($amount:expr) $(, $($rest:tt)*)?) => {
+        $crate::writers::pad(&mut $writer, $amount)?;
+        $crate::binwrite_manual_impl!(@items $self, $writer, $options, $($($rest)*)?);
+    };
+
+    (@items $self:ident, $writer:ident, $options:ident, align($alignment:expr) $(, $($rest:tt)*)?) => {
+        $crate::writers::align(&mut $writer, $alignment)?;
+        $crate::binwrite_manual_impl!(@items $self, $writer, $options, $($($rest)*)?);
+    };
+
+    (@items $self:ident, $writer:ident, $options:ident, trim_default($($field:ident),+ $(,)?) $(, $($rest:tt)*)?) => {
+        let mut keep = 0usize;
+        let mut index = 0usize;
+        $(
+            index += 1;
+            if $self.$field != Default::default() {
+                keep = index;
+            }
+        )+
+        let mut index = 0usize;
+        $(
+            index += 1;
+            if index <= keep {
+                $crate::BinWrite::write_options(&$self.$field, &mut $writer, &$options)?;
+            }
+        )+
+        $crate::binwrite_manual_impl!(@items $self, $writer, $options, $($($rest)*)?);
+    };
+
+    (@items $self:ident, $writer:ident, $options:ident, with($with:expr) $field:ident $(, $($rest:tt)*)?) => {
+        ($with)(&$self.$field, &mut $writer, &$options)?;
+        $crate::binwrite_manual_impl!(@items $self, $writer, $options, $($($rest)*)?);
+    };
+
+    (@items $self:ident, $writer:ident, $options:ident, $field:ident $(, $($rest:tt)*)?) => {
+        $crate::BinWrite::write_options(&$self.$field, &mut $writer, &$options)?;
+        $crate::binwrite_manual_impl!(@items $self, $writer, $options, $($($rest)*)?);
+    };
+
+    (@items $self:ident, $writer:ident, $options:ident,) => {};
+}
@@ -0,0 +1,83 @@
+use super::*;
+use std::io::{Error, ErrorKind};
+
+/// A set of named output streams, for formats that split a single structure across several files
+/// (such as a `.hdr` header and a `.dat` data file). Use [write_to](MultiStream::write_to) to
+/// route a value to a particular stream, or implement [BinWriteMulti] for a type that knows which
+/// stream each of its parts belongs in.
+///
+/// ```rust
+/// use std::io::{Result, Write};
+/// use binwrite::{BinWriteMulti, MultiStream, WriterOption};
+///
+/// struct Sound {
+///     sample_rate: u32,
+///     samples: Vec<i16>,
+/// }
+///
+/// impl BinWriteMulti for Sound {
+///     fn write_streams<W: Write>(&self, streams: &mut MultiStream<W>, options: &WriterOption) -> Result<()> {
+///         let data_size = (self.samples.len() * 2) as u32;
+///         streams.write_to("hdr", &(self.sample_rate, data_size), options)?;
+///         streams.write_to("dat", &self.samples, options)
+///     }
+/// }
+///
+/// let mut streams = MultiStream::new();
+/// streams.add("hdr", vec![]);
+/// streams.add("dat", vec![]);
+/// Sound { sample_rate: 44100, samples: vec![0, 1, -1] }
+///     .write_streams(&mut streams, &WriterOption::default())
+///     .unwrap();
+///
+/// assert_eq!(streams.stream("hdr").unwrap().len(), 8);
+/// assert_eq!(streams.stream("dat").unwrap().len(), 6);
+/// ```
+pub struct MultiStream<W: Write> {
+    streams: Vec<(String, W)>,
+}
+
+impl<W: Write> MultiStream<W> {
+    pub fn new() -> Self {
+        MultiStream { streams: Vec::new() }
+    }
+
+    /// Add a stream, replacing any existing stream with the same name
+    pub fn add<S: Into<String>>(&mut self, name: S, writer: W) {
+        let name = name.into();
+        match self.streams.iter_mut().find(|(existing, _)| *existing == name) {
+            Some((_, existing)) => *existing = writer,
+            None => self.streams.push((name, writer)),
+        }
+    }
+
+    /// The stream with the given name, returning an error if there isn't one
+    pub fn stream(&mut self, name: &str) -> Result<&mut W> {
+        self.streams
+            .iter_mut()
+            .find(|(existing, _)| existing == name)
+            .map(|(_, writer)| writer)
+            .ok_or_else(|| Error::new(ErrorKind::NotFound, format!("no output stream named {:?}", name)))
+    }
+
+    /// Write `value` to the stream with the given name
+    pub fn write_to<T: BinWrite + ?Sized>(&mut self, name: &str, value: &T, options: &WriterOption) -> Result<()> {
+        BinWrite::write_options(value, self.stream(name)?, options)
+    }
+
+    /// Unwrap the MultiStream, returning each stream with its name in the order they were added
+    pub fn into_inner(self) -> Vec<(String, W)> {
+        self.streams
+    }
+}
+
+impl<W: Write> Default for MultiStream<W> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A trait for types which are written across several named streams. See [MultiStream].
+pub trait BinWriteMulti {
+    fn write_streams<W: Write>(&self, streams: &mut MultiStream<W>, options: &WriterOption) -> Result<()>;
+}
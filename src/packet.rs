@@ -0,0 +1,239 @@
+//! # Example:
+//! ```rust
+//! use binwrite::packet::{FieldSize, PacketBuilder, PseudoHeader, Scope};
+//!
+//! let (src, dst) = ([192, 168, 0, 1], [192, 168, 0, 199]);
+//!
+//! let mut packet = PacketBuilder::new();
+//! // IPv4 header: total length at offset 2, header checksum at offset 10
+//! packet.push(&(
+//!     0x45u8, 0u8, 0u16, 0u16, 0x4000u16, 0x40u8, 17u8, 0u16, src, dst
+//! )).unwrap()
+//!     .length(2, FieldSize::U16, Scope::WholeLayer)
+//!     .checksum(10, Scope::Header, None);
+//! // UDP header: length at offset 4, checksum over a pseudo-header, the header and the payload
+//! packet.push(&(1234u16, 5678u16, 0u16, 0u16)).unwrap()
+//!     .length(4, FieldSize::U16, Scope::WholeLayer)
+//!     .checksum(6, Scope::WholeLayer, Some(PseudoHeader::Ipv4 { src, dst, protocol: 17 }));
+//! packet.payload(b"hello");
+//!
+//! let bytes = packet.build().unwrap();
+//! assert_eq!(bytes.len(), 20 + 8 + 5);
+//! assert_eq!(&bytes[2..4], &[0, 33]);
+//! assert_eq!(&bytes[24..26], &[0, 13]);
+//! ```
+use super::*;
+use crate::checksum::Checksum;
+use std::io::{Error, ErrorKind};
+
+/// The RFC 1071 internet checksum (ones' complement of the ones' complement sum of 16-bit big
+/// endian words), as used by IPv4, ICMP, UDP and TCP
+pub fn internet_checksum(data: &[u8]) -> u16 {
+    !fold_ones_complement(ones_complement_sum(0, data))
+}
+
+pub(crate) fn ones_complement_sum(mut sum: u32, data: &[u8]) -> u32 {
+    for word in data.chunks(2) {
+        let word = match word {
+            [high, low] => u16::from_be_bytes([*high, *low]),
+            [high] => u16::from_be_bytes([*high, 0]),
+            _ => unreachable!(),
+        };
+        sum += word as u32;
+        sum = (sum & 0xFFFF) + (sum >> 16);
+    }
+    sum
+}
+
+pub(crate) fn fold_ones_complement(mut sum: u32) -> u16 {
+    while sum > 0xFFFF {
+        sum = (sum & 0xFFFF) + (sum >> 16);
+    }
+    sum as u16
+}
+
+/// The pseudo-header included in UDP and TCP checksums. The length is filled in automatically.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PseudoHeader {
+    Ipv4 { src: [u8; 4], dst: [u8; 4], protocol: u8 },
+    Ipv6 { src: [u8; 16], dst: [u8; 16], next_header: u8 },
+}
+
+impl PseudoHeader {
+    /// Whether the pseudo-header is for a UDP datagram, where a computed checksum of 0 is sent as
+    /// 0xFFFF because 0 means no checksum (RFC 768)
+    pub(crate) fn is_udp(&self) -> bool {
+        const UDP: u8 = 17;
+        match self {
+            PseudoHeader::Ipv4 { protocol, .. } => *protocol == UDP,
+            PseudoHeader::Ipv6 { next_header, .. } => *next_header == UDP,
+        }
+    }
+
+    /// The bytes of the pseudo-header for an upper layer packet of `len` bytes. Returns an error
+    /// if `len` does not fit in the pseudo-header's length field.
+    pub fn to_bytes(&self, len: usize) -> Result<Vec<u8>> {
+        let mut bytes = vec![];
+        match self {
+            PseudoHeader::Ipv4 { src, dst, protocol } => {
+                bytes.extend_from_slice(src);
+                bytes.extend_from_slice(dst);
+                bytes.extend_from_slice(&[0, *protocol]);
+                bytes.extend_from_slice(&writers::len_as::<u16>(len)?.to_be_bytes());
+            }
+            PseudoHeader::Ipv6 { src, dst, next_header } => {
+                bytes.extend_from_slice(src);
+                bytes.extend_from_slice(dst);
+                bytes.extend_from_slice(&writers::len_as::<u32>(len)?.to_be_bytes());
+                bytes.extend_from_slice(&[0, 0, 0, *next_header]);
+            }
+        }
+        Ok(bytes)
+    }
+}
+
+/// The size of a length field
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FieldSize {
+    U8,
+    U16,
+    U32,
+}
+
+/// Which bytes a length or checksum field covers
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Scope {
+    /// Only the layer's own header
+    Header,
+    /// The layer's header and every layer after it
+    WholeLayer,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct LengthField {
+    offset: usize,
+    size: FieldSize,
+    scope: Scope,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct ChecksumField {
+    offset: usize,
+    scope: Scope,
+    pseudo_header: Option<PseudoHeader>,
+}
+
+/// A single layer of a packet, with the length and checksum fields to fill in when it is built
+#[derive(Clone, Debug)]
+pub struct Layer {
+    bytes: Vec<u8>,
+    length: Option<LengthField>,
+    checksum: Option<ChecksumField>,
+}
+
+impl Layer {
+    /// Fill in the big endian length field at `offset` within this layer
+    pub fn length(&mut self, offset: usize, size: FieldSize, scope: Scope) -> &mut Self {
+        self.length = Some(LengthField { offset, size, scope });
+        self
+    }
+
+    /// Fill in the internet checksum field at `offset` within this layer, optionally including a
+    /// pseudo-header in the checksum. With a UDP pseudo-header, a checksum of 0 is written as 0xFFFF.
+    pub fn checksum(&mut self, offset: usize, scope: Scope, pseudo_header: Option<PseudoHeader>) -> &mut Self {
+        self.checksum = Some(ChecksumField { offset, scope, pseudo_header });
+        self
+    }
+}
+
+/// A builder for network packets made up of layers (for example IPv4, then UDP, then a payload).
+/// Headers are written in network byte order, and length and internet checksum fields are filled
+/// in when the packet is built. Checksums are computed from the innermost layer outwards, so
+/// outer checksums cover the final contents of inner layers.
+#[derive(Clone, Debug, Default)]
+pub struct PacketBuilder {
+    layers: Vec<Layer>,
+}
+
+impl PacketBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a header as the next layer of the packet, returning it so its length and checksum
+    /// fields can be described
+    pub fn push<T: BinWrite + ?Sized>(&mut self, header: &T) -> Result<&mut Layer> {
+        let mut bytes = vec![];
+        let options = WriterOption {
+            endian: Endian::Big,
+            ..Default::default()
+        };
+        BinWrite::write_options(header, &mut bytes, &options)?;
+        Ok(self.push_bytes(bytes))
+    }
+
+    /// Add raw bytes as the final payload of the packet
+    pub fn payload(&mut self, bytes: &[u8]) -> &mut Layer {
+        self.push_bytes(bytes.to_vec())
+    }
+
+    fn push_bytes(&mut self, bytes: Vec<u8>) -> &mut Layer {
+        self.layers.push(Layer { bytes, length: None, checksum: None });
+        self.layers.last_mut().unwrap()
+    }
+
+    /// Build the packet, filling in all length and checksum fields
+    pub fn build(&self) -> Result<Vec<u8>> {
+        let mut starts = vec![];
+        let mut packet = vec![];
+        for layer in &self.layers {
+            starts.push(packet.len());
+            packet.extend_from_slice(&layer.bytes);
+        }
+
+        let total_len = packet.len();
+        let range = |i: usize, scope: Scope| match scope {
+            Scope::Header => starts[i]..starts[i] + self.layers[i].bytes.len(),
+            Scope::WholeLayer => starts[i]..total_len,
+        };
+        let out_of_bounds = || Error::new(ErrorKind::InvalidInput, "packet field is outside of its layer");
+
+        for (i, layer) in self.layers.iter().enumerate() {
+            if let Some(field) = layer.length {
+                let len = range(i, field.scope).len();
+                let bytes = match field.size {
+                    FieldSize::U8 => vec![writers::len_as::<u8>(len)?],
+                    FieldSize::U16 => writers::len_as::<u16>(len)?.to_be_bytes().to_vec(),
+                    FieldSize::U32 => writers::len_as::<u32>(len)?.to_be_bytes().to_vec(),
+                };
+                if field.offset + bytes.len() > layer.bytes.len() {
+                    return Err(out_of_bounds());
+                }
+                let start = starts[i] + field.offset;
+                packet[start..start + bytes.len()].copy_from_slice(&bytes);
+            }
+        }
+
+        for (i, layer) in self.layers.iter().enumerate().rev() {
+            if let Some(field) = layer.checksum {
+                if field.offset + 2 > layer.bytes.len() {
+                    return Err(out_of_bounds());
+                }
+                let start = starts[i] + field.offset;
+                packet[start..start + 2].copy_from_slice(&[0, 0]);
+
+                let covered = &packet[range(i, field.scope)];
+                let checksum = Checksum::Internet(field.pseudo_header).checksum(covered)? as u16;
+                packet[start..start + 2].copy_from_slice(&checksum.to_be_bytes());
+            }
+        }
+
+        Ok(packet)
+    }
+}
+
+impl BinWrite for PacketBuilder {
+    fn write_options<W: Write>(&self, writer: &mut W, _options: &WriterOption) -> Result<()> {
+        writer.write_all(&self.build()?)
+    }
+}
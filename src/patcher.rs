@@ -0,0 +1,67 @@
+use super::*;
+use crate::scratch::ScratchBuffer;
+use std::fs::{File, OpenOptions};
+use std::io::{Error, ErrorKind, Seek, SeekFrom};
+use std::path::Path;
+
+/// A writer for rewriting specific fields or sections of existing output in place, for save
+/// editors and incremental archive updates where rewriting the whole file isn't an option.
+/// Each value is written to a buffer first and only written to the output if it is exactly the
+/// expected size, so a mismatched value never overwrites its neighbours.
+///
+/// ```rust
+/// use std::io::Cursor;
+/// use binwrite::{Endian, Patcher};
+///
+/// let mut patcher = Patcher::new(
+///     Cursor::new(vec![0u8; 8]),
+///     binwrite::writer_option_new!(endian: Endian::Big)
+/// );
+/// patcher.write_at(2, &0x1234u16).unwrap();
+/// assert!(patcher.write_sized_at(4, 2, &1u32).is_err());
+///
+/// assert_eq!(patcher.into_inner().into_inner(), vec![0, 0, 0x12, 0x34, 0, 0, 0, 0]);
+/// ```
+pub struct Patcher<W: Write + Seek> {
+    inner: W,
+    options: WriterOption,
+}
+
+impl<W: Write + Seek> Patcher<W> {
+    pub fn new(inner: W, options: WriterOption) -> Self {
+        Patcher { inner, options }
+    }
+
+    /// Write `value` at `offset`, using its [FixedSize] as the size of the region being replaced
+    pub fn write_at<T: BinWrite + FixedSize + ?Sized>(&mut self, offset: u64, value: &T) -> Result<()> {
+        self.write_sized_at(offset, T::SIZE, value)
+    }
+
+    /// Write `value` at `offset`, replacing a region of exactly `size` bytes. Returns an error
+    /// without writing anything if `value` is a different size.
+    pub fn write_sized_at<T: BinWrite + ?Sized>(&mut self, offset: u64, size: usize, value: &T) -> Result<()> {
+        let mut bytes = ScratchBuffer::new(&self.options);
+        BinWrite::write_options(value, &mut *bytes, &self.options)?;
+        if bytes.len() != size {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("patch at {:#x} is {:#x} bytes, expected {:#x}", offset, bytes.len(), size)
+            ));
+        }
+        self.inner.seek(SeekFrom::Start(offset))?;
+        self.inner.write_all(&bytes)
+    }
+
+    /// Unwrap the Patcher, returning the inner writer
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl Patcher<File> {
+    /// Open an existing file for patching, without truncating it
+    pub fn open<P: AsRef<Path>>(path: P, options: WriterOption) -> Result<Self> {
+        let file = OpenOptions::new().read(true).write(true).open(path)?;
+        Ok(Patcher::new(file, options))
+    }
+}
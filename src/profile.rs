@@ -0,0 +1,54 @@
+use super::*;
+use crate::write_track::WriteTrack;
+use crate::writers::pad_with;
+
+/// A set of write settings shared by every type in a format, so they can be defined once instead
+/// of repeating the same attributes on every struct. Writing a value with a profile writes it
+/// with the profile's endianness, then pads it to the profile's alignment using its fill byte.
+///
+/// ```rust
+/// use binwrite::{Endian, Profile};
+///
+/// const MY_FORMAT: Profile = Profile::new(Endian::Little, 8, 0xFF);
+///
+/// let mut bytes = vec![];
+/// Profile::GAMECUBE.write(&(1u32, 2u16), &mut bytes).unwrap();
+/// MY_FORMAT.write(&3u16, &mut bytes).unwrap();
+///
+/// assert_eq!(&bytes[..6], &[0, 0, 0, 1, 0, 2]);
+/// assert_eq!(bytes.len(), 0x20 + 8);
+/// assert_eq!(&bytes[0x20..], &[3, 0, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Profile {
+    pub endian: Endian,
+    /// Alignment each value is padded to, 1 for no padding
+    pub alignment: usize,
+    /// Byte used for padding
+    pub fill: u8,
+}
+
+impl Profile {
+    /// GameCube/Wii: big endian, 32 byte aligned, zero filled
+    pub const GAMECUBE: Profile = Profile::new(Endian::Big, 0x20, 0);
+
+    pub const fn new(endian: Endian, alignment: usize, fill: u8) -> Self {
+        Profile { endian, alignment, fill }
+    }
+
+    /// Options for writing with this profile's endianness
+    pub fn options(&self) -> WriterOption {
+        WriterOption {
+            endian: self.endian,
+            ..Default::default()
+        }
+    }
+
+    /// Write `value` using this profile
+    pub fn write<T: BinWrite + ?Sized, W: Write>(&self, value: &T, writer: &mut W) -> Result<()> {
+        let mut writer = WriteTrack::new(writer);
+        BinWrite::write_options(value, &mut writer, &self.options())?;
+        let padding = writers::padding_for(writer.pos() as u64, self.alignment)?;
+        pad_with(&mut writer, padding, self.fill)
+    }
+}
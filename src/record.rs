@@ -0,0 +1,253 @@
+//! # Example:
+//! ```rust
+//! use binwrite::checksum::Crc;
+//! use binwrite::record::{Framing, RecordStream};
+//!
+//! let framing = Framing::new(1, 2, Some(Crc::CRC8));
+//! let mut stream = RecordStream::new(vec![], framing, binwrite::writer_option_new!(endian: binwrite::Endian::Big));
+//! stream.write(1, &0xABu8).unwrap();
+//! stream.write(2, &"hi").unwrap();
+//!
+//! let bytes = stream.into_inner();
+//! assert_eq!(&bytes[..4], &[1, 0, 1, 0xAB]);
+//! assert_eq!(bytes.len(), 5 + 6);
+//! ```
+use super::*;
+use crate::checksum::Crc;
+use std::io::{Error, ErrorKind, Read, Seek, SeekFrom};
+
+/// How each record in a [RecordStream] is framed: an optional type tag, an optional payload
+/// length and an optional CRC following the payload. Tag and length sizes are in bytes (1, 2, 4
+/// or 8), with 0 leaving the field out. The CRC covers the tag, length and payload.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Framing {
+    pub tag_size: usize,
+    pub length_size: usize,
+    pub crc: Option<Crc>,
+}
+
+impl Framing {
+    /// A u32 payload length followed by the payload, then a CRC-32
+    pub const LENGTH_CRC32: Framing = Framing::new(0, 4, Some(Crc::CRC32));
+
+    pub const fn new(tag_size: usize, length_size: usize, crc: Option<Crc>) -> Self {
+        Framing { tag_size, length_size, crc }
+    }
+
+    /// Frame a serialized payload
+    pub fn frame(&self, tag: u64, payload: &[u8], options: &WriterOption) -> Result<Vec<u8>> {
+        let mut frame = vec![];
+        write_uint(&mut frame, tag, self.tag_size, options.endian, "record tag")?;
+        write_uint(&mut frame, payload.len() as u64, self.length_size, options.endian, "record length")?;
+        frame.extend_from_slice(payload);
+        if let Some(crc) = self.crc {
+            let checksum = crc.checksum(&frame);
+            write_uint(&mut frame, checksum, crc.byte_len(), options.endian, "record CRC")?;
+        }
+        Ok(frame)
+    }
+}
+
+/// Write the low `size` bytes of `value`, returning an error if it doesn't fit
+pub(crate) fn write_uint(bytes: &mut Vec<u8>, value: u64, size: usize, endian: Endian, name: &str) -> Result<()> {
+    if size > 8 || (size < 8 && value >> (size * 8) != 0) {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!("{} {:#x} does not fit in {} bytes", name, value, size)
+        ));
+    }
+    match endian.resolve() {
+        Endian::Big => bytes.extend_from_slice(&value.to_be_bytes()[8 - size..]),
+        _ => bytes.extend_from_slice(&value.to_le_bytes()[..size]),
+    }
+    Ok(())
+}
+
+/// A writer for a sequence of records, each framed according to a [Framing] and written (and
+/// flushed) as soon as it is added, for log files and other append-only formats.
+pub struct RecordStream<W: Write> {
+    inner: W,
+    framing: Framing,
+    options: WriterOption,
+    pos: u64,
+    count: usize,
+}
+
+impl<W: Write> RecordStream<W> {
+    pub fn new(inner: W, framing: Framing, options: WriterOption) -> Self {
+        Self::starting_at(inner, framing, options, 0)
+    }
+
+    /// Create a stream whose first record will be written at `pos`, for appending to existing
+    /// output
+    pub fn starting_at(inner: W, framing: Framing, options: WriterOption, pos: u64) -> Self {
+        RecordStream { inner, framing, options, pos, count: 0 }
+    }
+
+    /// Write and flush a record with the given type tag (ignored if the framing has no tag),
+    /// returning the position the record was written at
+    pub fn write<T: BinWrite + ?Sized>(&mut self, tag: u64, record: &T) -> Result<u64> {
+        let mut payload = vec![];
+        BinWrite::write_options(record, &mut payload, &self.options)?;
+        let frame = self.framing.frame(tag, &payload, &self.options)?;
+        self.inner.write_all(&frame)?;
+        self.inner.flush()?;
+
+        let pos = self.pos;
+        self.pos += frame.len() as u64;
+        self.count += 1;
+        Ok(pos)
+    }
+
+    /// The position the next record will be written at
+    pub fn pos(&self) -> u64 {
+        self.pos
+    }
+
+    /// The number of records written by this stream
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    pub fn framing(&self) -> &Framing {
+        &self.framing
+    }
+
+    /// Unwrap the RecordStream, returning the inner writer
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+/// Magic number at the very end of a [Journal]
+pub const JOURNAL_MAGIC: [u8; 4] = *b"BWJL";
+
+fn read_uint(bytes: &[u8], endian: Endian) -> u64 {
+    let mut buf = [0u8; 8];
+    match endian.resolve() {
+        Endian::Big => {
+            buf[8 - bytes.len()..].copy_from_slice(bytes);
+            u64::from_be_bytes(buf)
+        }
+        _ => {
+            buf[..bytes.len()].copy_from_slice(bytes);
+            u64::from_le_bytes(buf)
+        }
+    }
+}
+
+/// An append-only log of framed records followed by an index of record offsets and a footer:
+///
+/// `records... | offsets: [u64; count] | count: u64 | JOURNAL_MAGIC`
+///
+/// [open](Journal::open) validates the footer and index of an existing journal, records are then
+/// appended over the old index and [commit](Journal::commit) writes the updated index and
+/// footer. Integers are written using the options' endianness.
+///
+/// ```rust
+/// use std::io::Cursor;
+/// use binwrite::record::{Framing, Journal};
+///
+/// let options = binwrite::WriterOption::default();
+/// let mut journal = Journal::create(Cursor::new(vec![]), Framing::LENGTH_CRC32, options.clone()).unwrap();
+/// journal.append(0, &1u32).unwrap();
+/// journal.commit().unwrap();
+///
+/// let mut journal = Journal::open(journal.into_inner(), Framing::LENGTH_CRC32, options).unwrap();
+/// journal.append(0, &2u32).unwrap();
+/// journal.commit().unwrap();
+/// assert_eq!(journal.offsets(), &[0, 12]);
+/// ```
+pub struct Journal<F: Read + Write + Seek> {
+    inner: F,
+    framing: Framing,
+    options: WriterOption,
+    offsets: Vec<u64>,
+    end: u64,
+}
+
+impl<F: Read + Write + Seek> Journal<F> {
+    /// Start a new, empty journal in `inner`, which must be empty. Truncate an existing file
+    /// first (for example with [File::set_len](std::fs::File::set_len)), otherwise its old
+    /// contents would be left after the new footer.
+    pub fn create(mut inner: F, framing: Framing, options: WriterOption) -> Result<Self> {
+        if inner.seek(SeekFrom::End(0))? != 0 {
+            return Err(Error::new(ErrorKind::InvalidInput, "a journal can only be created in an empty file"));
+        }
+        let mut journal = Journal { inner, framing, options, offsets: vec![], end: 0 };
+        journal.commit()?;
+        Ok(journal)
+    }
+
+    /// Open an existing journal, validating its footer and index
+    pub fn open(mut inner: F, framing: Framing, options: WriterOption) -> Result<Self> {
+        let invalid = |message: &str| Error::new(ErrorKind::InvalidData, format!("invalid journal: {}", message));
+
+        let len = inner.seek(SeekFrom::End(0))?;
+        if len < 12 {
+            return Err(invalid("too short for a footer"));
+        }
+        let mut footer = [0u8; 12];
+        inner.seek(SeekFrom::Start(len - 12))?;
+        inner.read_exact(&mut footer)?;
+        if footer[8..] != JOURNAL_MAGIC {
+            return Err(invalid("footer magic does not match"));
+        }
+
+        let count = read_uint(&footer[..8], options.endian);
+        let index_len = count.checked_mul(8).filter(|index_len| *index_len <= len - 12)
+            .ok_or_else(|| invalid("index is larger than the file"))?;
+        let end = len - 12 - index_len;
+
+        let mut index = vec![0u8; index_len as usize];
+        inner.seek(SeekFrom::Start(end))?;
+        inner.read_exact(&mut index)?;
+        let offsets: Vec<u64> = index.chunks(8).map(|offset| read_uint(offset, options.endian)).collect();
+        if offsets.windows(2).any(|pair| pair[0] >= pair[1]) || offsets.last().is_some_and(|last| *last >= end) {
+            return Err(invalid("record offsets are out of order or out of bounds"));
+        }
+
+        Ok(Journal { inner, framing, options, offsets, end })
+    }
+
+    /// Append a record after the existing records, returning its offset. The index is not
+    /// updated until [commit](Journal::commit) is called.
+    pub fn append<T: BinWrite + ?Sized>(&mut self, tag: u64, record: &T) -> Result<u64> {
+        let mut payload = vec![];
+        BinWrite::write_options(record, &mut payload, &self.options)?;
+        let frame = self.framing.frame(tag, &payload, &self.options)?;
+
+        self.inner.seek(SeekFrom::Start(self.end))?;
+        self.inner.write_all(&frame)?;
+
+        let offset = self.end;
+        self.offsets.push(offset);
+        self.end += frame.len() as u64;
+        Ok(offset)
+    }
+
+    /// Write the index and footer after the last record and flush
+    pub fn commit(&mut self) -> Result<()> {
+        let mut footer = vec![];
+        for offset in &self.offsets {
+            write_uint(&mut footer, *offset, 8, self.options.endian, "record offset")?;
+        }
+        write_uint(&mut footer, self.offsets.len() as u64, 8, self.options.endian, "record count")?;
+        footer.extend_from_slice(&JOURNAL_MAGIC);
+
+        self.inner.seek(SeekFrom::Start(self.end))?;
+        self.inner.write_all(&footer)?;
+        self.inner.flush()
+    }
+
+    /// The offset of every record in the journal
+    pub fn offsets(&self) -> &[u64] {
+        &self.offsets
+    }
+
+    /// Unwrap the Journal, returning the inner file. Records appended since the last
+    /// [commit](Journal::commit) are not in the index.
+    pub fn into_inner(self) -> F {
+        self.inner
+    }
+}
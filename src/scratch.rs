@@ -0,0 +1,89 @@
+use super::*;
+use std::ops::{Deref, DerefMut};
+use std::sync::Mutex;
+
+/// A pool of reusable byte buffers for writers which have to write a value to memory before
+/// writing it out (such as [CRC appending](checksum::CrcAppended),
+/// [record encryption](writers::RecordEncryptor) and [finalize hooks](writers::Finalized)). Set
+/// [WriterOption::scratch] to share one across writes so that repeated writes reuse the same
+/// allocations instead of allocating every call.
+///
+/// ```rust
+/// use std::sync::Arc;
+/// use binwrite::{BinWrite, WriteScratch};
+/// use binwrite::checksum::{Crc, CrcAppended};
+///
+/// let scratch = Arc::new(WriteScratch::new());
+/// let options = binwrite::writer_option_new!(endian: binwrite::Endian::Big, scratch: Some(scratch.clone()));
+///
+/// for frame in 0..3u32 {
+///     let mut bytes = vec![];
+///     CrcAppended::new(&frame, Crc::CRC32).write_options(&mut bytes, &options).unwrap();
+/// }
+/// assert_eq!(scratch.pooled(), 1);
+/// ```
+#[derive(Default)]
+pub struct WriteScratch {
+    buffers: Mutex<Vec<Vec<u8>>>,
+}
+
+impl WriteScratch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Take an empty buffer from the pool, or allocate one if the pool is empty
+    pub fn take(&self) -> Vec<u8> {
+        self.buffers.lock().ok().and_then(|mut buffers| buffers.pop()).unwrap_or_default()
+    }
+
+    /// Return a buffer to the pool to be reused. It is cleared but keeps its capacity.
+    pub fn give(&self, mut buffer: Vec<u8>) {
+        buffer.clear();
+        if let Ok(mut buffers) = self.buffers.lock() {
+            buffers.push(buffer);
+        }
+    }
+
+    /// The number of buffers currently waiting in the pool
+    pub fn pooled(&self) -> usize {
+        self.buffers.lock().map_or(0, |buffers| buffers.len())
+    }
+}
+
+/// A buffer taken from the [WriteScratch] in a WriterOption, which is given back when dropped.
+/// Without a WriteScratch this is just a new `Vec<u8>`.
+pub(crate) struct ScratchBuffer<'a> {
+    scratch: Option<&'a WriteScratch>,
+    buffer: Vec<u8>,
+}
+
+impl<'a> ScratchBuffer<'a> {
+    pub(crate) fn new(options: &'a WriterOption) -> Self {
+        let scratch = options.scratch.as_deref();
+        let buffer = scratch.map(WriteScratch::take).unwrap_or_default();
+        ScratchBuffer { scratch, buffer }
+    }
+}
+
+impl Deref for ScratchBuffer<'_> {
+    type Target = Vec<u8>;
+
+    fn deref(&self) -> &Vec<u8> {
+        &self.buffer
+    }
+}
+
+impl DerefMut for ScratchBuffer<'_> {
+    fn deref_mut(&mut self) -> &mut Vec<u8> {
+        &mut self.buffer
+    }
+}
+
+impl Drop for ScratchBuffer<'_> {
+    fn drop(&mut self) {
+        if let Some(scratch) = self.scratch {
+            scratch.give(std::mem::take(&mut self.buffer));
+        }
+    }
+}
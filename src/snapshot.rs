@@ -0,0 +1,180 @@
+use super::*;
+use std::collections::BTreeMap;
+use std::io::{Error, ErrorKind, Seek, SeekFrom};
+use std::ops::Range;
+
+struct Snapshot {
+    label: String,
+    len: usize,
+    pos: usize,
+    /// The original value of every byte overwritten since the snapshot was taken, saved the
+    /// first time it changes
+    original: BTreeMap<usize, u8>,
+}
+
+/// How the output of a [SnapshotBuffer] has changed since a snapshot
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SnapshotDiff {
+    /// The length of the output when the snapshot was taken
+    pub old_len: usize,
+    /// The current length of the output
+    pub new_len: usize,
+    /// The ranges of bytes which differ from the snapshot, including any bytes added since
+    pub changed: Vec<Range<usize>>,
+}
+
+/// An in-memory output buffer which can take labeled snapshots of what has been written so far
+/// and later diff against or restore them. This allows speculative writes: snapshot, write one
+/// encoding, measure it, restore, then write another. Snapshots are copy-on-write, so taking one
+/// is cheap and only bytes overwritten afterwards are copied.
+///
+/// ```rust
+/// use binwrite::{BinWrite, SnapshotBuffer};
+///
+/// let mut buffer = SnapshotBuffer::new();
+/// 1u8.write(&mut buffer).unwrap();
+/// buffer.snapshot("header");
+///
+/// [2u8, 3, 4].write(&mut buffer).unwrap();
+/// assert_eq!(buffer.diff("header").unwrap().new_len, 4);
+///
+/// buffer.restore("header").unwrap();
+/// 5u8.write(&mut buffer).unwrap();
+/// assert_eq!(buffer.as_bytes(), &[1, 5]);
+/// ```
+#[derive(Default)]
+pub struct SnapshotBuffer {
+    data: Vec<u8>,
+    pos: usize,
+    snapshots: Vec<Snapshot>,
+}
+
+impl SnapshotBuffer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Take a snapshot of the current output and position. If a snapshot with the same label
+    /// already exists, the newest one is used by [restore](SnapshotBuffer::restore) and
+    /// [diff](SnapshotBuffer::diff).
+    pub fn snapshot<S: Into<String>>(&mut self, label: S) {
+        self.snapshots.push(Snapshot {
+            label: label.into(),
+            len: self.data.len(),
+            pos: self.pos,
+            original: BTreeMap::new(),
+        });
+    }
+
+    fn find(&self, label: &str) -> Result<usize> {
+        self.snapshots.iter()
+            .rposition(|snapshot| snapshot.label == label)
+            .ok_or_else(|| Error::new(ErrorKind::NotFound, format!("no snapshot labeled {:?}", label)))
+    }
+
+    /// Compare the current output to a snapshot
+    pub fn diff(&self, label: &str) -> Result<SnapshotDiff> {
+        let snapshot = &self.snapshots[self.find(label)?];
+        let mut changed: Vec<Range<usize>> = vec![];
+        let overwritten = snapshot.original.iter()
+            .filter(|(offset, byte)| self.data[**offset] != **byte)
+            .map(|(offset, _)| *offset..*offset + 1);
+        let appended = Some(snapshot.len..self.data.len()).filter(|range| !range.is_empty());
+        for range in overwritten.chain(appended) {
+            match changed.last_mut() {
+                Some(last) if last.end == range.start => last.end = range.end,
+                _ => changed.push(range),
+            }
+        }
+        Ok(SnapshotDiff { old_len: snapshot.len, new_len: self.data.len(), changed })
+    }
+
+    /// Roll the output and position back to a snapshot, discarding any snapshots taken after it.
+    /// The snapshot itself is kept, so it can be restored again.
+    pub fn restore(&mut self, label: &str) -> Result<()> {
+        let index = self.find(label)?;
+        self.snapshots.truncate(index + 1);
+        let snapshot = &mut self.snapshots[index];
+        self.data.truncate(snapshot.len);
+        for (offset, byte) in std::mem::take(&mut snapshot.original) {
+            self.data[offset] = byte;
+        }
+        self.pos = snapshot.pos;
+        Ok(())
+    }
+
+    /// Drop a snapshot once it is no longer needed, keeping the output as it is
+    pub fn release(&mut self, label: &str) -> Result<()> {
+        let index = self.find(label)?;
+        self.snapshots.remove(index);
+        Ok(())
+    }
+
+    /// The current write position
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.data
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.data
+    }
+}
+
+impl Write for SnapshotBuffer {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        if self.pos > self.data.len() {
+            self.data.resize(self.pos, 0);
+        }
+        let overwrite_end = self.data.len().min(self.pos + buf.len());
+        for snapshot in &mut self.snapshots {
+            for offset in self.pos..overwrite_end.min(snapshot.len) {
+                snapshot.original.entry(offset).or_insert(self.data[offset]);
+            }
+        }
+
+        let overlap = overwrite_end - self.pos;
+        self.data[self.pos..overwrite_end].copy_from_slice(&buf[..overlap]);
+        self.data.extend_from_slice(&buf[overlap..]);
+        self.pos += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl Seek for SnapshotBuffer {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        let pos = match pos {
+            SeekFrom::Start(pos) => Some(pos),
+            SeekFrom::End(offset) => (self.data.len() as u64).checked_add_signed(offset),
+            SeekFrom::Current(offset) => (self.pos as u64).checked_add_signed(offset),
+        };
+        match pos {
+            Some(pos) => {
+                self.pos = pos as usize;
+                Ok(pos)
+            }
+            None => Err(Error::new(ErrorKind::InvalidInput, "invalid seek to a negative position")),
+        }
+    }
+}
+
+impl BinWrite for SnapshotBuffer {
+    fn write_options<W: Write>(&self, writer: &mut W, _options: &WriterOption) -> Result<()> {
+        writer.write_all(&self.data)
+    }
+}
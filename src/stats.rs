@@ -0,0 +1,96 @@
+use super::*;
+use std::collections::BTreeMap;
+
+/// Statistics for a single type written using [StatsWriter::record]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TypeStats {
+    /// Number of values of this type recorded
+    pub count: usize,
+    /// Total bytes written by values of this type
+    pub bytes: usize,
+    /// The most bytes written by a single value of this type
+    pub largest: usize,
+}
+
+/// Statistics gathered by a [StatsWriter]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WriteStats {
+    /// Total bytes written
+    pub bytes: usize,
+    /// Number of calls to [write](std::io::Write::write) on the writer
+    pub write_calls: usize,
+    /// Per-type statistics for values written using [StatsWriter::record], by type name
+    pub types: BTreeMap<&'static str, TypeStats>,
+}
+
+impl WriteStats {
+    /// The type name and size of the single largest value recorded
+    pub fn largest(&self) -> Option<(&'static str, usize)> {
+        self.types.iter()
+            .map(|(name, stats)| (*name, stats.largest))
+            .max_by_key(|(_, largest)| *largest)
+    }
+}
+
+/// A writer which gathers statistics about what is written through it, for seeing where output
+/// size and call overhead come from. Writing through it counts bytes and write calls, writing
+/// values with [record](StatsWriter::record) additionally tracks them by type.
+///
+/// ```rust
+/// use binwrite::stats::StatsWriter;
+///
+/// let mut writer = StatsWriter::new(vec![]);
+/// writer.record(&vec![0u32; 4], &Default::default()).unwrap();
+/// writer.record(&String::from("name"), &Default::default()).unwrap();
+///
+/// let stats = writer.stats();
+/// assert_eq!(stats.bytes, 20);
+/// assert_eq!(stats.largest(), Some((std::any::type_name::<Vec<u32>>(), 16)));
+/// ```
+pub struct StatsWriter<W: Write> {
+    inner: W,
+    stats: WriteStats,
+}
+
+impl<W: Write> StatsWriter<W> {
+    pub fn new(inner: W) -> Self {
+        StatsWriter {
+            inner, stats: WriteStats::default()
+        }
+    }
+
+    /// Write `value`, recording the number of bytes written under its type name
+    pub fn record<T: BinWrite + ?Sized>(&mut self, value: &T, options: &WriterOption) -> Result<()> {
+        let start = self.stats.bytes;
+        BinWrite::write_options(value, self, options)?;
+        let written = self.stats.bytes - start;
+
+        let stats = self.stats.types.entry(std::any::type_name::<T>()).or_default();
+        stats.count += 1;
+        stats.bytes += written;
+        stats.largest = stats.largest.max(written);
+        Ok(())
+    }
+
+    pub fn stats(&self) -> &WriteStats {
+        &self.stats
+    }
+
+    /// Unwrap the StatsWriter, returning the inner writer and the statistics gathered
+    pub fn into_inner(self) -> (W, WriteStats) {
+        (self.inner, self.stats)
+    }
+}
+
+impl<W: Write> Write for StatsWriter<W> {
+    fn write(&mut self, data: &[u8]) -> Result<usize> {
+        let amount = self.inner.write(data)?;
+        self.stats.bytes += amount;
+        self.stats.write_calls += 1;
+        Ok(amount)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.inner.flush()
+    }
+}
@@ -0,0 +1,71 @@
+//! Helpers for fuzzing and property testing BinWrite implementations, available with the
+//! `arbitrary` and `proptest` features.
+use super::*;
+use std::fmt::Debug;
+
+/// Write `value`, panicking if writing returns an error, and return the bytes written
+pub fn assert_writes<T: BinWrite + Debug + ?Sized>(value: &T, options: &WriterOption) -> Vec<u8> {
+    let mut bytes = vec![];
+    if let Err(err) = BinWrite::write_options(value, &mut bytes, options) {
+        panic!("failed to write {:?}: {}", value, err);
+    }
+    bytes
+}
+
+/// Write `value` and check that the number of bytes written matches its [FixedSize]
+pub fn assert_writes_fixed_size<T: BinWrite + FixedSize + Debug + ?Sized>(value: &T, options: &WriterOption) -> Vec<u8> {
+    let bytes = assert_writes(value, options);
+    assert_eq!(
+        bytes.len(), T::SIZE,
+        "{:?} wrote {:#x} bytes but its FixedSize::SIZE is {:#x}", value, bytes.len(), T::SIZE
+    );
+    bytes
+}
+
+/// Write `value` and check that reading the bytes back with `read` (for example a binread
+/// implementation) produces an equal value
+pub fn assert_roundtrip<T, F, E>(value: &T, options: &WriterOption, read: F)
+    where T: BinWrite + PartialEq + Debug,
+          F: FnOnce(&[u8]) -> std::result::Result<T, E>,
+          E: Debug,
+{
+    let bytes = assert_writes(value, options);
+    match read(&bytes) {
+        Ok(read_value) => assert_eq!(&read_value, value, "value changed after a roundtrip"),
+        Err(err) => panic!("failed to read back {:?}: {:?}", value, err),
+    }
+}
+
+/// Generate a `T` from fuzzer input and check that it writes successfully, returning the bytes
+/// written. Returns None if the input isn't enough to generate a `T`.
+///
+/// ```rust,ignore
+/// fuzz_target!(|data: &[u8]| {
+///     binwrite::testing::fuzz_write::<MyStruct>(data, &Default::default());
+/// });
+/// ```
+#[cfg(feature = "arbitrary")]
+pub fn fuzz_write<T>(data: &[u8], options: &WriterOption) -> Option<Vec<u8>>
+    where T: BinWrite + Debug + for<'a> arbitrary::Arbitrary<'a>,
+{
+    let value = T::arbitrary(&mut arbitrary::Unstructured::new(data)).ok()?;
+    Some(assert_writes(&value, options))
+}
+
+/// Run `check` against randomly generated values of `T` using proptest, panicking with the
+/// minimal failing value if any check fails
+#[cfg(feature = "proptest")]
+pub fn proptest_write<T, F>(options: &WriterOption, check: F)
+    where T: BinWrite + Debug + proptest::arbitrary::Arbitrary,
+          F: Fn(&T, &[u8]),
+{
+    let mut runner = proptest::test_runner::TestRunner::default();
+    let result = runner.run(&proptest::arbitrary::any::<T>(), |value| {
+        let bytes = assert_writes(&value, options);
+        check(&value, &bytes);
+        Ok(())
+    });
+    if let Err(err) = result {
+        panic!("{}", err);
+    }
+}
@@ -5,17 +5,44 @@ use std::ops::{Deref, DerefMut};
 /// position and implementing [Seek](std::io::Seek), but only for seeks that go nowhere. This
 /// allows you to keep track of how many bytes have been written since the creation of the
 /// WriteTrack. Can be thought of as a looser, albeit weaker, [Cursor](std::io::Cursor)
+///
+/// If the inner writer implements Seek, use [new_seekable](WriteTrack::new_seekable) to forward
+/// all seeks to it instead. Positions are then relative to where the inner writer was when the
+/// WriteTrack was created.
 pub struct WriteTrack<W: Write> {
     inner: W,
     pos: usize,
+    start: u64,
+    seek: Option<fn(&mut W, SeekFrom) -> Result<u64>>,
 }
 
 impl<W: Write> WriteTrack<W> {
     pub fn new(inner: W) -> Self {
         WriteTrack {
-            inner, pos: 0
+            inner, pos: 0, start: 0, seek: None
         }
     }
+
+    /// The current position, which is the number of bytes written since the creation of the
+    /// WriteTrack unless it has been seeked
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+
+    /// Unwrap the WriteTrack, returning the inner writer
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: Write + Seek> WriteTrack<W> {
+    /// Create a WriteTrack which forwards seeks to the inner writer, keeping its position in sync
+    pub fn new_seekable(mut inner: W) -> Result<Self> {
+        let start = inner.stream_position()?;
+        Ok(WriteTrack {
+            inner, pos: 0, start, seek: Some(<W as Seek>::seek)
+        })
+    }
 }
 
 impl<W: Write> Deref for WriteTrack<W> {
@@ -46,8 +73,21 @@ impl<W: Write> Write for WriteTrack<W> {
 
 impl<W: Write> Seek for WriteTrack<W> {
     fn seek(&mut self, from: SeekFrom) -> Result<u64> {
-        match from {
-            SeekFrom::Current(0) | SeekFrom::End(0) => {
+        match (self.seek, from) {
+            (Some(seek), _) => {
+                let from = match from {
+                    SeekFrom::Start(offset) => SeekFrom::Start(self.start + offset),
+                    from => from,
+                };
+                let pos = seek(&mut self.inner, from)?;
+                if pos < self.start {
+                    seek(&mut self.inner, SeekFrom::Start(self.start + self.pos as u64))?;
+                    return Err(std::io::Error::from(std::io::ErrorKind::InvalidInput));
+                }
+                self.pos = (pos - self.start) as usize;
+                Ok(self.pos as u64)
+            }
+            (None, SeekFrom::Current(0)) | (None, SeekFrom::End(0)) => {
                 Ok(self.pos as u64)
             }
             _ => {
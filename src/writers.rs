@@ -1,18 +1,122 @@
 use super::*;
 
+/// BMP and TGA image writing
+pub mod bitmap;
+/// BPS patch writing
+pub mod bps;
+/// GameCube/Wii DOL executable and REL module writing
+pub mod dol;
+/// ELF-style section layout
+pub mod elf;
+/// Intel HEX record writing
+pub mod intel_hex;
+/// IPS patch writing
+pub mod ips;
+/// STL and PLY mesh writing
+pub mod mesh;
+/// RIFF and WAV file writing
+pub mod riff;
+/// N64 and SNES ROM header checksums
+pub mod rom;
+/// Motorola S-record writing
+pub mod srec;
+/// USTAR tar archive writing
+pub mod tar;
+/// ZIP archive writing
+pub mod zip;
+
+mod align;
+mod ascii;
+mod assert;
+mod best_of;
+mod bits;
+mod bom;
+mod chunk;
+mod chunked;
+mod constants;
+mod delta;
+mod dns;
+#[cfg(feature = "encoding_rs")]
+mod encoded;
+mod encrypt;
+mod endian_only;
+mod hooks;
+mod len_of;
+mod len_prefix;
+mod localized;
+mod mask;
+mod math;
+mod offset_table;
+mod option;
+mod payload;
+mod pcm;
+mod pixel;
+mod pool;
+mod repeat;
+mod reverse;
+mod rle;
+mod scramble;
+mod sparse;
+mod stream;
+mod swizzle;
+mod terminated;
+mod version;
+mod word_swap;
+#[cfg(feature = "yaz0")]
+mod yaz0;
+
+pub use align::*;
+pub use ascii::*;
+pub use assert::*;
+pub use best_of::*;
+pub use bits::*;
+pub use bom::*;
+pub use chunk::*;
+pub use constants::*;
+pub use delta::*;
+pub use dns::*;
+#[cfg(feature = "encoding_rs")]
+pub use encoded::*;
+pub use encrypt::*;
+pub use endian_only::*;
+pub use hooks::*;
+pub use len_of::*;
+pub use len_prefix::*;
+pub use localized::*;
+pub use mask::*;
+pub use math::*;
+pub use offset_table::*;
+pub use option::*;
+pub use payload::*;
+pub use pcm::*;
+pub use pixel::*;
+pub use pool::*;
+pub use repeat::*;
+pub use reverse::*;
+pub use rle::*;
+pub use scramble::*;
+pub use sparse::*;
+pub use stream::*;
+pub use swizzle::*;
+pub use terminated::*;
+pub use version::*;
+pub use word_swap::*;
+#[cfg(feature = "yaz0")]
+pub use yaz0::*;
+
 /// A built in writer for null terminated utf8 strings. Use `#[binwrite(cstr)]` as a shortcut for
-/// this.
+/// this. The string is formatted through a fixed buffer rather than into a `String`, so memory use
+/// stays flat for large values.
 pub fn null_terminated_string<S: std::fmt::Display, W: Write>(string: S, writer: &mut W, options: &WriterOption) -> Result<()> {
-    BinWrite::write_options(&format!("{}", string), writer, options)?;
+    chunked::ChunkedText::utf8(writer, options).write_display(&string)?;
     BinWrite::write_options(&0u8, writer, options)
 }
 
-/// A built in writer for utf16 strings. Use `#[binwrite(utf16)]` as a shortcut for this.
+/// A built in writer for utf16 strings. Use `#[binwrite(utf16)]` as a shortcut for this. The
+/// string is encoded through a fixed buffer rather than converted all at once, so memory use stays
+/// flat for large values.
 pub fn utf16_string<S: std::fmt::Display, W: Write>(string: S, writer: &mut W, options: &WriterOption) -> Result<()> {
-    for c in format!("{}", string)[..].encode_utf16() {
-        BinWrite::write_options(&c, writer, options)?;
-    }
-    Ok(())
+    chunked::ChunkedText::utf16(writer, options).write_display(&string)
 }
 
 /// A built in writer for null-terminated utf16 strings. Use `#[binwrite(utf16_null)]` as a
@@ -22,3 +126,19 @@ pub fn utf16_null_string<S: std::fmt::Display, W: Write>(string: S, writer: &mut
     BinWrite::write_options(&0u16, writer, options)
 }
 
+
+/// A built in writer for writing a char as UTF-16 code unit(s), using the endianness for byte
+/// order. Use `#[binwrite(with(writers::utf16_char))]`.
+pub fn utf16_char<W: Write>(c: &char, writer: &mut W, options: &WriterOption) -> Result<()> {
+    let mut buf = [0u16; 2];
+    for unit in c.encode_utf16(&mut buf).iter() {
+        BinWrite::write_options(unit, writer, options)?;
+    }
+    Ok(())
+}
+
+/// A built in writer for writing a char as its u32 Unicode scalar value, using the endianness for
+/// byte order. Use `#[binwrite(with(writers::u32_char))]`.
+pub fn u32_char<W: Write>(c: &char, writer: &mut W, options: &WriterOption) -> Result<()> {
+    BinWrite::write_options(&(*c as u32), writer, options)
+}
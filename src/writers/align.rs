@@ -0,0 +1,107 @@
+use super::*;
+use std::io::{Error, ErrorKind, Seek, SeekFrom};
+use crate::write_track::WriteTrack;
+
+/// A wrapper which writes the inner value followed by enough padding to make the number of bytes
+/// written a multiple of `alignment`. Wrapping every record of a `Vec<T>` in this will keep each
+/// record aligned regardless of which field comes last. Use
+/// `#[binwrite(preprocessor(align_after(0x20)))]` to align a single field.
+pub struct Aligned<'a, T: ?Sized> {
+    value: &'a T,
+    alignment: usize,
+}
+
+impl<'a, T: ?Sized> Aligned<'a, T> {
+    pub fn new(value: &'a T, alignment: usize) -> Self {
+        Aligned { value, alignment }
+    }
+}
+
+impl<'a, T: BinWrite + ?Sized> BinWrite for Aligned<'a, T> {
+    fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+        let mut writer = WriteTrack::new(writer);
+        BinWrite::write_options(self.value, &mut writer, options)?;
+        let padding = padding_for(writer.pos() as u64, self.alignment)?;
+        pad(&mut writer, padding)
+    }
+}
+
+/// A preprocessor for padding a value to a multiple of `alignment` bytes. See [Aligned].
+pub fn align_after<T: BinWrite + ?Sized>(alignment: usize) -> impl Fn(&T) -> Aligned<'_, T> {
+    move |value| Aligned::new(value, alignment)
+}
+
+/// A shared block of zeroes, so padding can be written without allocating
+static ZEROES: [u8; 0x1000] = [0; 0x1000];
+
+/// Write `amount` bytes of zero padding. Unlike `#[binwrite(pad(...))]` the amount can be computed
+/// at runtime, such as from another field of the struct.
+pub fn pad<W: Write>(writer: &mut W, mut amount: usize) -> Result<()> {
+    while amount > 0 {
+        let chunk = amount.min(ZEROES.len());
+        writer.write_all(&ZEROES[..chunk])?;
+        amount -= chunk;
+    }
+    Ok(())
+}
+
+/// Same as [pad], but padding with `fill` instead of zeroes
+pub fn pad_with<W: Write>(writer: &mut W, mut amount: usize, fill: u8) -> Result<()> {
+    if fill == 0 {
+        return pad(writer, amount);
+    }
+    let block = [fill; 0x100];
+    while amount > 0 {
+        let chunk = amount.min(block.len());
+        writer.write_all(&block[..chunk])?;
+        amount -= chunk;
+    }
+    Ok(())
+}
+
+/// Same as [pad], but seeks over the padding instead of writing it (only the last byte is
+/// written, so the output is extended). When writing to a file this leaves a hole which most
+/// filesystems store sparsely, making large alignments (such as to 1 MiB boundaries) cheap.
+pub fn pad_sparse<W: Write + Seek>(writer: &mut W, amount: usize) -> Result<()> {
+    if amount > 0 {
+        writer.seek(SeekFrom::Current(amount as i64 - 1))?;
+        writer.write_all(&[0])?;
+    }
+    Ok(())
+}
+
+/// Write zero padding until the position of the writer is a multiple of `alignment`. Unlike
+/// `#[binwrite(align(...))]` the alignment can be computed at runtime, for formats which store
+/// their alignment granularity in a header. Returns an error if `alignment` is zero.
+pub fn align<W: Write + Seek>(writer: &mut W, alignment: usize) -> Result<()> {
+    let pos = writer.stream_position()?;
+    let padding = padding_for(pos, alignment)?;
+    pad(writer, padding)
+}
+
+pub(crate) fn padding_for(pos: u64, alignment: usize) -> Result<usize> {
+    if alignment == 0 {
+        return Err(Error::new(ErrorKind::InvalidInput, "alignment must be non-zero"));
+    }
+    let alignment = alignment as u64;
+    Ok(((alignment - (pos % alignment)) % alignment) as usize)
+}
+
+/// Write each item in order, padding each item to a multiple of `B::ALIGNMENT` so that every
+/// item stays aligned like in a C array
+pub(crate) fn write_items<B: BinWrite, W: Write>(items: &[B], writer: &mut W, options: &WriterOption) -> Result<()> {
+    if B::ALIGNMENT <= 1 {
+        for item in items {
+            BinWrite::write_options(item, writer, options)?;
+        }
+        return Ok(());
+    }
+
+    let mut writer = WriteTrack::new(writer);
+    for item in items {
+        BinWrite::write_options(item, &mut writer, options)?;
+        let padding = padding_for(writer.pos() as u64, B::ALIGNMENT)?;
+        pad(&mut writer, padding)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,178 @@
+use super::*;
+use std::fmt::{Display, LowerHex, Octal, UpperHex};
+use std::io::{Error, ErrorKind};
+
+/// The base numbers are written in by [AsciiNumber]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Radix {
+    Decimal,
+    /// Hexadecimal using lowercase letters
+    LowerHex,
+    /// Hexadecimal using uppercase letters
+    UpperHex,
+    Octal,
+}
+
+/// A wrapper which writes a number as ASCII text, right-aligned in a field of `width` characters
+/// and padded on the left with `fill` (usually `b'0'` or `b' '`), like `%08X` in C. Writing
+/// returns an error if the number doesn't fit in the field. As in C, negative numbers written in
+/// hex or octal are written as their two's complement. Endianness has no effect. Use
+/// `#[binwrite(preprocessor(ascii_hex(8)))]` to write a field as 8 uppercase hex digits.
+pub struct AsciiNumber<'a, T: ?Sized> {
+    value: &'a T,
+    radix: Radix,
+    width: usize,
+    fill: u8,
+}
+
+impl<'a, T: ?Sized> AsciiNumber<'a, T> {
+    pub fn new(value: &'a T, radix: Radix, width: usize, fill: u8) -> Self {
+        AsciiNumber { value, radix, width, fill }
+    }
+}
+
+impl<'a, T> BinWrite for AsciiNumber<'a, T>
+    where T: Display + LowerHex + UpperHex + Octal + ?Sized,
+{
+    fn write_options<W: Write>(&self, writer: &mut W, _options: &WriterOption) -> Result<()> {
+        let digits = match self.radix {
+            Radix::Decimal => format!("{}", self.value),
+            Radix::LowerHex => format!("{:x}", self.value),
+            Radix::UpperHex => format!("{:X}", self.value),
+            Radix::Octal => format!("{:o}", self.value),
+        };
+        if digits.len() > self.width {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("{} does not fit in {} ASCII digits", digits, self.width)
+            ));
+        }
+        // Like printf, zero padding goes between the sign and the digits
+        let padding = self.width - digits.len();
+        match digits.strip_prefix('-') {
+            Some(magnitude) if self.fill == b'0' => {
+                writer.write_all(b"-")?;
+                pad_with(writer, padding, self.fill)?;
+                writer.write_all(magnitude.as_bytes())
+            }
+            _ => {
+                pad_with(writer, padding, self.fill)?;
+                writer.write_all(digits.as_bytes())
+            }
+        }
+    }
+}
+
+/// A preprocessor for writing a number as ASCII text. See [AsciiNumber].
+pub fn ascii<T>(radix: Radix, width: usize, fill: u8) -> impl Fn(&T) -> AsciiNumber<'_, T>
+    where T: Display + LowerHex + UpperHex + Octal + ?Sized,
+{
+    move |value| AsciiNumber::new(value, radix, width, fill)
+}
+
+/// A preprocessor for writing a number as zero-padded uppercase ASCII hex, like `%0*X`
+pub fn ascii_hex<T>(width: usize) -> impl Fn(&T) -> AsciiNumber<'_, T>
+    where T: Display + LowerHex + UpperHex + Octal + ?Sized,
+{
+    ascii(Radix::UpperHex, width, b'0')
+}
+
+/// A preprocessor for writing a number as zero-padded ASCII decimal, like `%0*d`
+pub fn ascii_decimal<T>(width: usize) -> impl Fn(&T) -> AsciiNumber<'_, T>
+    where T: Display + LowerHex + UpperHex + Octal + ?Sized,
+{
+    ascii(Radix::Decimal, width, b'0')
+}
+
+/// A preprocessor for writing a number as zero-padded ASCII octal, like `%0*o`
+pub fn ascii_octal<T>(width: usize) -> impl Fn(&T) -> AsciiNumber<'_, T>
+    where T: Display + LowerHex + UpperHex + Octal + ?Sized,
+{
+    ascii(Radix::Octal, width, b'0')
+}
+
+/// How [AsciiFloat] writes a float
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FloatFormat {
+    /// Fixed-point with the given number of digits after the decimal point, like `%.6f`
+    Fixed(usize),
+    /// Scientific notation with the given number of digits after the decimal point and a signed
+    /// exponent of at least two digits, like `%.6e` (`1.500000e+00`)
+    Scientific(usize),
+}
+
+impl FloatFormat {
+    /// Format a value the way C's printf would. Infinities are written as `inf`/`-inf` and NaN as
+    /// `nan`.
+    pub fn format(&self, value: f64) -> String {
+        if value.is_nan() {
+            return String::from("nan");
+        }
+        if value.is_infinite() {
+            return String::from(if value < 0.0 { "-inf" } else { "inf" });
+        }
+        match *self {
+            FloatFormat::Fixed(precision) => format!("{:.*}", precision, value),
+            FloatFormat::Scientific(precision) => {
+                let formatted = format!("{:.*e}", precision, value);
+                let (mantissa, exponent) = formatted.split_once('e').unwrap_or((&formatted, "0"));
+                let exponent: i32 = exponent.parse().unwrap_or(0);
+                let sign = if exponent < 0 { '-' } else { '+' };
+                format!("{}e{}{:02}", mantissa, sign, exponent.abs())
+            }
+        }
+    }
+}
+
+/// A wrapper which writes floats as ASCII text, for text variants of formats (such as ASCII PLY
+/// or STL) whose binary variant writes the same fields as raw floats. Slices and `Vec`s are
+/// written with a space between each value. Endianness has no effect. Use
+/// `#[binwrite(preprocessor(ascii_float(FloatFormat::Fixed(6))))]`.
+pub struct AsciiFloat<'a, T: ?Sized> {
+    value: &'a T,
+    format: FloatFormat,
+}
+
+impl<'a, T: ?Sized> AsciiFloat<'a, T> {
+    pub fn new(value: &'a T, format: FloatFormat) -> Self {
+        AsciiFloat { value, format }
+    }
+}
+
+/// Internal macro for implementing BinWrite for AsciiFloat of a float type and slices of it
+macro_rules! ascii_float_impl {
+    ($($float:ty),*) => {
+        $(
+            impl<'a> BinWrite for AsciiFloat<'a, $float> {
+                fn write_options<W: Write>(&self, writer: &mut W, _options: &WriterOption) -> Result<()> {
+                    writer.write_all(self.format.format(*self.value as f64).as_bytes())
+                }
+            }
+
+            impl<'a> BinWrite for AsciiFloat<'a, [$float]> {
+                fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+                    for (i, value) in self.value.iter().enumerate() {
+                        if i > 0 {
+                            writer.write_all(b" ")?;
+                        }
+                        AsciiFloat::new(value, self.format).write_options(writer, options)?;
+                    }
+                    Ok(())
+                }
+            }
+
+            impl<'a> BinWrite for AsciiFloat<'a, Vec<$float>> {
+                fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+                    AsciiFloat::new(&self.value[..], self.format).write_options(writer, options)
+                }
+            }
+        )*
+    }
+}
+
+ascii_float_impl!(f32, f64);
+
+/// A preprocessor for writing floats as ASCII text. See [AsciiFloat].
+pub fn ascii_float<T: ?Sized>(format: FloatFormat) -> impl Fn(&T) -> AsciiFloat<'_, T> {
+    move |value| AsciiFloat::new(value, format)
+}
@@ -0,0 +1,93 @@
+use super::*;
+use std::io::{Error, ErrorKind, Seek};
+use crate::FixedSize;
+use crate::write_track::WriteTrack;
+
+/// A wrapper which writes the inner value and then checks that exactly `size` bytes were
+/// written, returning an error otherwise. Use `#[binwrite(preprocessor(assert_size(0x40)))]` to
+/// check the size of a field, or wrap a whole struct with [SizeChecked::new] to check its size.
+pub struct SizeChecked<'a, T: ?Sized> {
+    value: &'a T,
+    size: usize,
+}
+
+impl<'a, T: ?Sized> SizeChecked<'a, T> {
+    pub fn new(value: &'a T, size: usize) -> Self {
+        SizeChecked { value, size }
+    }
+}
+
+impl<'a, T: BinWrite + ?Sized> BinWrite for SizeChecked<'a, T> {
+    fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+        let mut writer = WriteTrack::new(writer);
+        BinWrite::write_options(self.value, &mut writer, options)?;
+        if writer.pos() == self.size {
+            Ok(())
+        } else {
+            Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("expected to write {:#x} bytes, wrote {:#x}", self.size, writer.pos())
+            ))
+        }
+    }
+}
+
+/// A preprocessor for checking the number of bytes a field writes. See [SizeChecked].
+pub fn assert_size<T: BinWrite + ?Sized>(size: usize) -> impl Fn(&T) -> SizeChecked<'_, T> {
+    move |value| SizeChecked::new(value, size)
+}
+
+/// Check that the current position of the writer is `offset`, returning an error otherwise. The
+/// position is whatever the writer's [Seek](std::io::Seek) implementation reports, so for a
+/// [WriteTrack] it is the number of bytes written since the WriteTrack was created.
+pub fn assert_offset<W: Seek>(writer: &mut W, offset: u64) -> Result<()> {
+    let pos = writer.stream_position()?;
+    if pos == offset {
+        Ok(())
+    } else {
+        Err(Error::new(
+            ErrorKind::InvalidData,
+            format!("expected to be at offset {:#x}, was at {:#x}", offset, pos)
+        ))
+    }
+}
+
+/// A wrapper which, in debug builds only, checks that writing the inner value produces exactly
+/// [FixedSize::SIZE](crate::FixedSize::SIZE) bytes and panics otherwise. This catches a
+/// `FixedSize` implementation that has drifted from the `BinWrite` implementation it describes.
+/// In release builds the value is written directly with no overhead. Use
+/// `#[binwrite(preprocessor(check_layout))]` to check a field.
+pub struct LayoutChecked<'a, T: ?Sized> {
+    value: &'a T,
+}
+
+impl<'a, T: ?Sized> LayoutChecked<'a, T> {
+    pub fn new(value: &'a T) -> Self {
+        LayoutChecked { value }
+    }
+}
+
+impl<'a, T: BinWrite + FixedSize + ?Sized> BinWrite for LayoutChecked<'a, T> {
+    #[cfg(debug_assertions)]
+    fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+        let mut writer = WriteTrack::new(writer);
+        BinWrite::write_options(self.value, &mut writer, options)?;
+        assert_eq!(
+            writer.pos(), T::SIZE,
+            "{} wrote {:#x} bytes but its FixedSize::SIZE is {:#x}",
+            std::any::type_name::<T>(), writer.pos(), T::SIZE
+        );
+        Ok(())
+    }
+
+    #[cfg(not(debug_assertions))]
+    fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+        BinWrite::write_options(self.value, writer, options)
+    }
+}
+
+/// A preprocessor for checking a field against its FixedSize in debug builds. See
+/// [LayoutChecked].
+pub fn check_layout<T: BinWrite + FixedSize + ?Sized>(value: &T) -> LayoutChecked<'_, T> {
+    LayoutChecked::new(value)
+}
@@ -0,0 +1,68 @@
+use super::*;
+use crate::SnapshotBuffer;
+
+/// A candidate writer for [BestOf]. Generic writers can be passed with the writer type filled in,
+/// such as `writers::utf16_string::<&str, SnapshotBuffer>`.
+pub type Candidate<T> = fn(&T, &mut SnapshotBuffer, &WriterOption) -> Result<()>;
+
+/// A wrapper which writes the inner value with each candidate writer into a scratch
+/// [SnapshotBuffer] and then writes out the result with the lowest score. By default the score
+/// is the size in bytes, so the smallest encoding wins. Ties go to the earliest candidate.
+/// Useful for picking compression levels or encodings per value. Use
+/// `#[binwrite(preprocessor(best_of(&[write_a, write_b])))]`.
+pub struct BestOf<'a, T: ?Sized> {
+    value: &'a T,
+    candidates: Vec<Candidate<T>>,
+    score: fn(&[u8]) -> u64,
+}
+
+impl<'a, T: ?Sized> BestOf<'a, T> {
+    /// Pick the candidate which writes the fewest bytes
+    pub fn new(value: &'a T, candidates: &[Candidate<T>]) -> Self {
+        Self::scored(value, candidates, |bytes| bytes.len() as u64)
+    }
+
+    /// Pick the candidate whose output has the lowest `score`
+    pub fn scored(value: &'a T, candidates: &[Candidate<T>], score: fn(&[u8]) -> u64) -> Self {
+        BestOf { value, candidates: candidates.to_vec(), score }
+    }
+
+    /// Write every candidate and return the index of the best one along with its output
+    pub fn select(&self, options: &WriterOption) -> Result<(usize, Vec<u8>)> {
+        let mut scratch = SnapshotBuffer::new();
+        scratch.snapshot("candidate");
+        let mut best: Option<(usize, u64)> = None;
+        for (index, candidate) in self.candidates.iter().enumerate() {
+            candidate(self.value, &mut scratch, options)?;
+            let score = (self.score)(scratch.as_bytes());
+            let better = match best {
+                None => true,
+                Some((_, best_score)) => score < best_score,
+            };
+            if better {
+                best = Some((index, score));
+            }
+            scratch.restore("candidate")?;
+        }
+
+        let (index, _) = best.ok_or_else(|| {
+            std::io::Error::new(std::io::ErrorKind::InvalidInput, "no candidate writers to choose from")
+        })?;
+        (self.candidates[index])(self.value, &mut scratch, options)?;
+        Ok((index, scratch.into_bytes()))
+    }
+}
+
+impl<'a, T: ?Sized> BinWrite for BestOf<'a, T> {
+    fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+        let (_, bytes) = self.select(options)?;
+        writer.write_all(&bytes)
+    }
+}
+
+/// A preprocessor for writing a value with whichever candidate writer produces the smallest
+/// output. See [BestOf].
+pub fn best_of<T: ?Sized>(candidates: &[Candidate<T>]) -> impl Fn(&T) -> BestOf<'_, T> {
+    let candidates = candidates.to_vec();
+    move |value| BestOf::new(value, &candidates)
+}
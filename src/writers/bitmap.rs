@@ -0,0 +1,131 @@
+//! # Example:
+//! ```rust
+//! use binwrite::BinWrite;
+//! use binwrite::writers::bitmap::{Bmp, Tga};
+//!
+//! // A 2x2 image of RGBA pixels, top row first
+//! let pixels = [[255, 0, 0, 255], [0, 255, 0, 255], [0, 0, 255, 255], [255, 255, 255, 255]];
+//!
+//! let mut bmp = vec![];
+//! Bmp::new(2, 2, &pixels).write(&mut bmp).unwrap();
+//! assert_eq!(&bmp[..2], b"BM");
+//! // 24-bit rows of 6 bytes are padded to 8
+//! assert_eq!(bmp.len(), 14 + 40 + 8 * 2);
+//!
+//! let mut tga = vec![];
+//! Tga::new(2, 2, &pixels).write(&mut tga).unwrap();
+//! assert_eq!(tga.len(), 18 + 4 * 4);
+//! ```
+use super::*;
+use std::io::{Error, ErrorKind};
+
+fn check_dimensions(width: usize, height: usize, pixels: &[[u8; 4]]) -> Result<()> {
+    if width.checked_mul(height) != Some(pixels.len()) {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!("{} pixels can't make up a {}x{} image", pixels.len(), width, height)
+        ));
+    }
+    Ok(())
+}
+
+/// A Windows bitmap of RGBA pixels (top row first), written as 24-bit BGR, or 32-bit BGRA if
+/// `alpha` is set. Rows are stored bottom-up and padded to a multiple of 4 bytes. BMP is always
+/// little endian, regardless of the options.
+pub struct Bmp<'a> {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: &'a [[u8; 4]],
+    pub alpha: bool,
+}
+
+impl<'a> Bmp<'a> {
+    pub fn new(width: usize, height: usize, pixels: &'a [[u8; 4]]) -> Self {
+        Bmp { width, height, pixels, alpha: false }
+    }
+}
+
+impl<'a> BinWrite for Bmp<'a> {
+    fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+        check_dimensions(self.width, self.height, self.pixels)?;
+        let options = WriterOption {
+            endian: Endian::Little,
+            ..options.clone()
+        };
+
+        let pixel_size = if self.alpha { 4 } else { 3 };
+        let row_size = self.width * pixel_size;
+        let row_padding = padding_for(row_size as u64, 4)?;
+        let image_size: u32 = len_as((row_size + row_padding) * self.height)?;
+        let data_offset = 14u32 + 40;
+
+        // BITMAPFILEHEADER
+        (*b"BM", data_offset + image_size, 0u32, data_offset).write_options(writer, &options)?;
+        // BITMAPINFOHEADER, with a 2835 pixels per meter (72 DPI) resolution
+        (
+            40u32, len_as::<i32>(self.width)?, len_as::<i32>(self.height)?, 1u16,
+            (pixel_size * 8) as u16, 0u32, image_size, 2835i32, 2835i32, 0u32, 0u32,
+        ).write_options(writer, &options)?;
+
+        if self.width == 0 {
+            return Ok(());
+        }
+        let mut bgr = Vec::with_capacity(row_size + row_padding);
+        for row in self.pixels.chunks(self.width).rev() {
+            bgr.clear();
+            for [r, g, b, a] in row {
+                bgr.extend_from_slice(&[*b, *g, *r, *a][..pixel_size]);
+            }
+            bgr.resize(row_size + row_padding, 0);
+            writer.write_all(&bgr)?;
+        }
+        Ok(())
+    }
+}
+
+/// A Truevision TGA image of RGBA pixels (top row first), written as 32-bit BGRA with a top-left
+/// origin, optionally run-length encoded one row at a time. TGA is always little endian,
+/// regardless of the options.
+pub struct Tga<'a> {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: &'a [[u8; 4]],
+    pub rle: bool,
+}
+
+impl<'a> Tga<'a> {
+    pub fn new(width: usize, height: usize, pixels: &'a [[u8; 4]]) -> Self {
+        Tga { width, height, pixels, rle: false }
+    }
+}
+
+impl<'a> BinWrite for Tga<'a> {
+    fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+        check_dimensions(self.width, self.height, self.pixels)?;
+        let options = WriterOption {
+            endian: Endian::Little,
+            ..options.clone()
+        };
+
+        let image_type: u8 = if self.rle { 10 } else { 2 };
+        // No ID or color map, then the image spec: origin, size, 32 bits per pixel and a
+        // descriptor of 8 alpha bits with a top-left origin
+        (
+            0u8, 0u8, image_type, [0u8; 5], 0u16, 0u16, len_as::<u16>(self.width)?,
+            len_as::<u16>(self.height)?, 32u8, 0x28u8,
+        ).write_options(writer, &options)?;
+
+        if self.width == 0 {
+            return Ok(());
+        }
+        for row in self.pixels.chunks(self.width) {
+            let bgra: Vec<u8> = row.iter().flat_map(|[r, g, b, a]| [*b, *g, *r, *a]).collect();
+            if self.rle {
+                writer.write_all(&Rle::tga(4).encode(&bgra)?)?;
+            } else {
+                writer.write_all(&bgra)?;
+            }
+        }
+        Ok(())
+    }
+}
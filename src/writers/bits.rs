@@ -0,0 +1,218 @@
+use super::*;
+use std::io::{Error, ErrorKind};
+
+/// The order in which bits are packed into each byte
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BitOrder {
+    /// The first bit is the most significant bit of the byte
+    Msb0,
+    /// The first bit is the least significant bit of the byte
+    Lsb0,
+}
+
+/// A wrapper which packs bools into bits, 8 per byte, with the final byte padded with zero bits.
+/// The output is then padded with zero bytes to a multiple of `pad_to` bytes, for bitsets stored
+/// as an array of u16/u32 words. Use `#[binwrite(preprocessor(bitset(BitOrder::Msb0, 1)))]` to
+/// pack a `Vec<bool>` or bool array.
+pub struct Bitset<'a> {
+    bits: &'a [bool],
+    order: BitOrder,
+    pad_to: usize,
+}
+
+impl<'a> Bitset<'a> {
+    pub fn new(bits: &'a [bool], order: BitOrder, pad_to: usize) -> Self {
+        Bitset { bits, order, pad_to }
+    }
+}
+
+impl<'a> BinWrite for Bitset<'a> {
+    fn write_options<W: Write>(&self, writer: &mut W, _options: &WriterOption) -> Result<()> {
+        let mut bytes: Vec<u8> = self.bits.chunks(8)
+            .map(|chunk| {
+                chunk.iter()
+                    .enumerate()
+                    .filter(|(_, bit)| **bit)
+                    .fold(0u8, |byte, (i, _)| {
+                        byte | match self.order {
+                            BitOrder::Msb0 => 0x80 >> i,
+                            BitOrder::Lsb0 => 1 << i,
+                        }
+                    })
+            })
+            .collect();
+        let pad_to = self.pad_to.max(1);
+        let padded_len = bytes.len().div_ceil(pad_to) * pad_to;
+        bytes.resize(padded_len, 0);
+        writer.write_all(&bytes)
+    }
+}
+
+/// A preprocessor for packing bools into bits. See [Bitset].
+pub fn bitset<C: AsRef<[bool]> + ?Sized>(order: BitOrder, pad_to: usize) -> impl Fn(&C) -> Bitset<'_> {
+    move |bits| Bitset::new(bits.as_ref(), order, pad_to)
+}
+
+/// The order in which two 4-bit values are packed into each byte
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NibbleOrder {
+    /// The first value is stored in the high nibble of the byte
+    HighFirst,
+    /// The first value is stored in the low nibble of the byte
+    LowFirst,
+}
+
+/// A wrapper which packs 4-bit values two per byte, with an odd number of values padded with a
+/// zero nibble. Writing returns an error if any value is greater than 0xF. Use
+/// `#[binwrite(preprocessor(nibbles(NibbleOrder::HighFirst)))]` to pack a `Vec<u8>`.
+pub struct Nibbles<'a> {
+    values: &'a [u8],
+    order: NibbleOrder,
+}
+
+impl<'a> Nibbles<'a> {
+    pub fn new(values: &'a [u8], order: NibbleOrder) -> Self {
+        Nibbles { values, order }
+    }
+}
+
+impl<'a> BinWrite for Nibbles<'a> {
+    fn write_options<W: Write>(&self, writer: &mut W, _options: &WriterOption) -> Result<()> {
+        if let Some(value) = self.values.iter().find(|value| **value > 0xF) {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("{:#x} does not fit in a nibble", value)
+            ));
+        }
+        let bytes: Vec<u8> = self.values.chunks(2)
+            .map(|pair| {
+                let first = pair[0];
+                let second = pair.get(1).copied().unwrap_or(0);
+                match self.order {
+                    NibbleOrder::HighFirst => (first << 4) | second,
+                    NibbleOrder::LowFirst => (second << 4) | first,
+                }
+            })
+            .collect();
+        writer.write_all(&bytes)
+    }
+}
+
+/// A preprocessor for packing 4-bit values two per byte. See [Nibbles].
+pub fn nibbles<C: AsRef<[u8]> + ?Sized>(order: NibbleOrder) -> impl Fn(&C) -> Nibbles<'_> {
+    move |values| Nibbles::new(values.as_ref(), order)
+}
+
+/// A writer for packing values into individual bits, for formats such as codec headers which mix
+/// bit fields with byte-aligned data. Bits are packed in `order` within each byte, with each
+/// value's most significant bit first for [Msb0](BitOrder::Msb0) and least significant bit first
+/// for [Lsb0](BitOrder::Lsb0). Use [align](BitWriter::align) to flush to a byte or word boundary
+/// before writing byte-aligned data through the BitWriter's [Write] impl, which is an error while
+/// mid-byte.
+///
+/// ```rust
+/// use binwrite::BinWrite;
+/// use binwrite::writers::{BitOrder, BitWriter};
+///
+/// let mut bytes = vec![];
+/// let mut bits = BitWriter::new(&mut bytes, BitOrder::Msb0);
+/// bits.write_bits(0b101, 3).unwrap();
+/// bits.write_bool(true).unwrap();
+/// bits.align(16).unwrap();
+/// 0xABu8.write(&mut bits).unwrap();
+/// bits.finish().unwrap();
+///
+/// assert_eq!(bytes, [0b1011_0000, 0, 0xAB]);
+/// ```
+pub struct BitWriter<W: Write> {
+    inner: W,
+    order: BitOrder,
+    partial: u8,
+    partial_len: u32,
+    bit_pos: u64,
+}
+
+impl<W: Write> BitWriter<W> {
+    pub fn new(inner: W, order: BitOrder) -> Self {
+        BitWriter { inner, order, partial: 0, partial_len: 0, bit_pos: 0 }
+    }
+
+    /// The number of bits written since the BitWriter was created
+    pub fn bit_pos(&self) -> u64 {
+        self.bit_pos
+    }
+
+    fn push_bit(&mut self, bit: bool) -> Result<()> {
+        if bit {
+            self.partial |= match self.order {
+                BitOrder::Msb0 => 0x80 >> self.partial_len,
+                BitOrder::Lsb0 => 1 << self.partial_len,
+            };
+        }
+        self.partial_len += 1;
+        self.bit_pos += 1;
+        if self.partial_len == 8 {
+            self.inner.write_all(&[self.partial])?;
+            self.partial = 0;
+            self.partial_len = 0;
+        }
+        Ok(())
+    }
+
+    /// Write the low `count` bits of `value`. Returns an error if `count` is more than 64 or
+    /// `value` doesn't fit in `count` bits.
+    pub fn write_bits(&mut self, value: u64, count: u32) -> Result<()> {
+        if count > 64 || (count < 64 && value >> count != 0) {
+            return Err(Error::new(ErrorKind::InvalidData, format!("{:#x} does not fit in {} bits", value, count)));
+        }
+        for i in 0..count {
+            let shift = match self.order {
+                BitOrder::Msb0 => count - 1 - i,
+                BitOrder::Lsb0 => i,
+            };
+            self.push_bit((value >> shift) & 1 != 0)?;
+        }
+        Ok(())
+    }
+
+    pub fn write_bool(&mut self, bit: bool) -> Result<()> {
+        self.push_bit(bit)
+    }
+
+    /// Write zero bits until the bit position is a multiple of `bits`, such as 8 to reach the next
+    /// byte or 16 for the next 16-bit word. Returns an error if `bits` is zero.
+    pub fn align(&mut self, bits: u64) -> Result<()> {
+        if bits == 0 {
+            return Err(Error::new(ErrorKind::InvalidInput, "bit alignment must be non-zero"));
+        }
+        let padding = (bits - self.bit_pos % bits) % bits;
+        for _ in 0..padding {
+            self.push_bit(false)?;
+        }
+        Ok(())
+    }
+
+    /// Pad the final byte with zero bits and return the inner writer
+    pub fn finish(mut self) -> Result<W> {
+        self.align(8)?;
+        Ok(self.inner)
+    }
+}
+
+impl<W: Write> Write for BitWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        if self.partial_len != 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("cannot write bytes at bit position {}, align to a byte first", self.bit_pos)
+            ));
+        }
+        let written = self.inner.write(buf)?;
+        self.bit_pos += written as u64 * 8;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.inner.flush()
+    }
+}
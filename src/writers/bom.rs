@@ -0,0 +1,43 @@
+use super::*;
+
+/// The byte order mark written by [ByteOrderMarked], reading as `FE FF` when big endian and
+/// `FF FE` when little endian
+pub const BOM: u16 = 0xFEFF;
+
+/// A wrapper for dual-endian formats (such as SARC or BYAML) which writes a byte order marker
+/// using `endian`, then writes the inner value with `endian` overriding the current endianness.
+/// Use `#[binwrite(preprocessor(byte_order_marked(self.endian)))]` to select the endianness of
+/// the rest of a file from a single runtime flag.
+pub struct ByteOrderMarked<'a, T: ?Sized> {
+    value: &'a T,
+    endian: Endian,
+    marker: u16,
+}
+
+impl<'a, T: ?Sized> ByteOrderMarked<'a, T> {
+    /// Mark the byte order with [BOM]
+    pub fn new(value: &'a T, endian: Endian) -> Self {
+        Self::with_marker(value, endian, BOM)
+    }
+
+    /// Mark the byte order with a custom marker, such as `0x4259` for BYAML's `"BY"`/`"YB"`
+    pub fn with_marker(value: &'a T, endian: Endian, marker: u16) -> Self {
+        ByteOrderMarked { value, endian, marker }
+    }
+}
+
+impl<'a, T: BinWrite + ?Sized> BinWrite for ByteOrderMarked<'a, T> {
+    fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+        let options = WriterOption {
+            endian: self.endian,
+            ..options.clone()
+        };
+        BinWrite::write_options(&self.marker, writer, &options)?;
+        BinWrite::write_options(self.value, writer, &options)
+    }
+}
+
+/// A preprocessor for writing a byte order mark and switching endianness. See [ByteOrderMarked].
+pub fn byte_order_marked<T: BinWrite + ?Sized>(endian: Endian) -> impl Fn(&T) -> ByteOrderMarked<'_, T> {
+    move |value| ByteOrderMarked::new(value, endian)
+}
@@ -0,0 +1,133 @@
+//! # Example:
+//! ```rust
+//! use binwrite::BinWrite;
+//! use binwrite::writers::bps::{BpsAction, BpsPatch};
+//!
+//! let source = b"hello world";
+//! let target = b"hello there";
+//!
+//! let patch = BpsPatch::from_diff(source, target);
+//! assert_eq!(patch.actions, vec![
+//!     BpsAction::SourceRead { len: 6 },
+//!     BpsAction::TargetRead { data: b"there".to_vec() },
+//! ]);
+//!
+//! let mut bytes = vec![];
+//! patch.write(&mut bytes).unwrap();
+//! assert_eq!(&bytes[..4], b"BPS1");
+//! ```
+use super::*;
+use crate::checksum::Crc;
+use std::io::{Error, ErrorKind};
+
+/// Write a BPS variable-length integer
+pub fn write_varint<W: Write>(mut value: u64, writer: &mut W) -> Result<()> {
+    loop {
+        let x = (value & 0x7F) as u8;
+        value >>= 7;
+        if value == 0 {
+            return writer.write_all(&[0x80 | x]);
+        }
+        writer.write_all(&[x])?;
+        value -= 1;
+    }
+}
+
+/// Write a BPS signed relative offset, stored as a varint of the magnitude with the sign in the
+/// lowest bit
+pub fn write_signed_varint<W: Write>(value: i64, writer: &mut W) -> Result<()> {
+    write_varint((value.unsigned_abs() << 1) | (value < 0) as u64, writer)
+}
+
+/// A single action of a BPS patch. Copy offsets are relative to the end of the previous copy of
+/// the same kind, as they are stored in the patch.
+#[derive(Clone, Debug, PartialEq)]
+pub enum BpsAction {
+    /// Copy `len` bytes from the same position in the source
+    SourceRead { len: u64 },
+    /// Write `data` to the target
+    TargetRead { data: Vec<u8> },
+    /// Copy `len` bytes from the source, moving the source copy position by `offset` first
+    SourceCopy { len: u64, offset: i64 },
+    /// Copy `len` bytes from earlier in the target, moving the target copy position by `offset`
+    /// first
+    TargetCopy { len: u64, offset: i64 },
+}
+
+impl BinWrite for BpsAction {
+    fn write_options<W: Write>(&self, writer: &mut W, _options: &WriterOption) -> Result<()> {
+        let (len, command) = match self {
+            BpsAction::SourceRead { len } => (*len, 0),
+            BpsAction::TargetRead { data } => (data.len() as u64, 1),
+            BpsAction::SourceCopy { len, .. } => (*len, 2),
+            BpsAction::TargetCopy { len, .. } => (*len, 3),
+        };
+        if len == 0 {
+            return Err(Error::new(ErrorKind::InvalidData, "BPS actions cannot be empty"));
+        }
+        write_varint(((len - 1) << 2) | command, writer)?;
+
+        match self {
+            BpsAction::SourceRead { .. } => Ok(()),
+            BpsAction::TargetRead { data } => writer.write_all(data),
+            BpsAction::SourceCopy { offset, .. } | BpsAction::TargetCopy { offset, .. } => {
+                write_signed_varint(*offset, writer)
+            }
+        }
+    }
+}
+
+/// A BPS patch. The CRC32s of the source and target are stored in the footer so the patch can be
+/// validated when applied, the CRC32 of the patch itself is computed when writing.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BpsPatch {
+    pub source_size: u64,
+    pub target_size: u64,
+    pub metadata: Vec<u8>,
+    pub actions: Vec<BpsAction>,
+    pub source_crc: u32,
+    pub target_crc: u32,
+}
+
+impl BpsPatch {
+    /// Create a patch which turns `source` into `target`, using SourceRead wherever the bytes
+    /// match in place and TargetRead otherwise
+    pub fn from_diff(source: &[u8], target: &[u8]) -> Self {
+        let mut actions: Vec<BpsAction> = vec![];
+        for (i, byte) in target.iter().enumerate() {
+            let matches = source.get(i) == Some(byte);
+            match (actions.last_mut(), matches) {
+                (Some(BpsAction::SourceRead { len }), true) => *len += 1,
+                (Some(BpsAction::TargetRead { data }), false) => data.push(*byte),
+                (_, true) => actions.push(BpsAction::SourceRead { len: 1 }),
+                (_, false) => actions.push(BpsAction::TargetRead { data: vec![*byte] }),
+            }
+        }
+
+        BpsPatch {
+            source_size: source.len() as u64,
+            target_size: target.len() as u64,
+            metadata: vec![],
+            actions,
+            source_crc: Crc::CRC32.checksum(source) as u32,
+            target_crc: Crc::CRC32.checksum(target) as u32,
+        }
+    }
+}
+
+impl BinWrite for BpsPatch {
+    fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+        let mut patch = b"BPS1".to_vec();
+        write_varint(self.source_size, &mut patch)?;
+        write_varint(self.target_size, &mut patch)?;
+        write_varint(self.metadata.len() as u64, &mut patch)?;
+        patch.extend_from_slice(&self.metadata);
+        BinWrite::write_options(&self.actions, &mut patch, options)?;
+        patch.extend_from_slice(&self.source_crc.to_le_bytes());
+        patch.extend_from_slice(&self.target_crc.to_le_bytes());
+        let patch_crc = Crc::CRC32.checksum(&patch) as u32;
+        patch.extend_from_slice(&patch_crc.to_le_bytes());
+
+        writer.write_all(&patch)
+    }
+}
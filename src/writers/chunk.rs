@@ -0,0 +1,144 @@
+use super::*;
+use crate::checksum::{Crc, CrcDigest};
+use crate::scratch::ScratchBuffer;
+use std::io::{Seek, SeekFrom};
+
+/// A four character code, such as the chunk IDs of RIFF and IFF files
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct FourCC(pub [u8; 4]);
+
+impl From<&[u8; 4]> for FourCC {
+    fn from(code: &[u8; 4]) -> Self {
+        FourCC(*code)
+    }
+}
+
+impl BinWrite for FourCC {
+    fn write_options<W: Write>(&self, writer: &mut W, _options: &WriterOption) -> Result<()> {
+        writer.write_all(&self.0)
+    }
+}
+
+impl FixedSize for FourCC {
+    const SIZE: usize = 4;
+}
+
+/// A wrapper which writes the inner value as a RIFF/IFF-style chunk: a [FourCC] ID, a u32 size
+/// (using the endianness, little for RIFF and big for IFF), the data and a padding byte if the
+/// data has an odd length. Use `#[binwrite(preprocessor(chunk(b"data")))]` to write a field as a
+/// chunk.
+pub struct Chunk<'a, T: ?Sized> {
+    id: FourCC,
+    value: &'a T,
+}
+
+impl<'a, T: ?Sized> Chunk<'a, T> {
+    pub fn new<I: Into<FourCC>>(id: I, value: &'a T) -> Self {
+        Chunk { id: id.into(), value }
+    }
+}
+
+impl<'a, T: BinWrite + ?Sized> BinWrite for Chunk<'a, T> {
+    fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+        let mut data = ScratchBuffer::new(options);
+        BinWrite::write_options(self.value, &mut *data, options)?;
+        let size: u32 = len_as(data.len())?;
+        (self.id, size).write_options(writer, options)?;
+        writer.write_all(&data)?;
+        pad(writer, data.len() % 2)
+    }
+}
+
+/// A preprocessor for writing a field as a chunk. See [Chunk].
+pub fn chunk<T: BinWrite + ?Sized>(id: &[u8; 4]) -> impl Fn(&T) -> Chunk<'_, T> {
+    let id = FourCC(*id);
+    move |value| Chunk::new(id, value)
+}
+
+/// A guard for writing a chunk imperatively, for manual impls which can't build the chunk's data
+/// as a single value. [begin](ChunkScope::begin) writes the [FourCC] ID and a placeholder size,
+/// data is written through the scope (which implements Write and Seek), and
+/// [end](ChunkScope::end) backfills the size and pads odd-sized data, using the same layout as
+/// [Chunk]. Chunks can be nested by beginning a chunk on another scope. A scope which is dropped
+/// without calling `end` leaves the size as 0.
+///
+/// ```rust
+/// use std::io::Cursor;
+/// use binwrite::BinWrite;
+/// use binwrite::writers::ChunkScope;
+///
+/// let options = binwrite::writer_option_new!(endian: binwrite::Endian::Little);
+/// let mut writer = Cursor::new(vec![]);
+/// let mut list = ChunkScope::begin(&mut writer, b"LIST", &options).unwrap();
+/// let mut data = ChunkScope::begin(&mut list, b"DATA", &options).unwrap();
+/// (1u8, 2u16).write_options(&mut data, &options).unwrap();
+/// data.end().unwrap();
+/// list.end().unwrap();
+///
+/// assert_eq!(writer.into_inner(), b"LIST\x0c\0\0\0DATA\x03\0\0\0\x01\x02\0\0");
+/// ```
+pub struct ChunkScope<'w, W: Write + Seek> {
+    writer: &'w mut W,
+    start: u64,
+    options: WriterOption,
+    crc: Option<(Crc, CrcDigest)>,
+}
+
+impl<'w, W: Write + Seek> ChunkScope<'w, W> {
+    /// Write the chunk's ID and a placeholder size, and start the chunk's data
+    pub fn begin<I: Into<FourCC>>(writer: &'w mut W, id: I, options: &WriterOption) -> Result<Self> {
+        let start = writer.stream_position()?;
+        (id.into(), 0u32).write_options(writer, options)?;
+        Ok(ChunkScope { writer, start, options: options.clone(), crc: None })
+    }
+
+    /// Same as [begin](ChunkScope::begin), but [end](ChunkScope::end) also writes a CRC of the
+    /// data after it (not counted in the size). Because the CRC is computed from the bytes as they
+    /// are written, a chunk with a CRC can't contain nested chunks or be seeked within.
+    pub fn begin_with_crc<I: Into<FourCC>>(writer: &'w mut W, id: I, crc: Crc, options: &WriterOption) -> Result<Self> {
+        let mut scope = Self::begin(writer, id, options)?;
+        scope.crc = Some((crc, crc.digest()));
+        Ok(scope)
+    }
+
+    /// Finish the chunk, writing the CRC if there is one, backfilling the size and padding the
+    /// data to an even length
+    pub fn end(self) -> Result<()> {
+        let end = self.writer.stream_position()?;
+        let size: u32 = len_as((end - self.start - 8) as usize)?;
+        if let Some((crc, digest)) = &self.crc {
+            let checksum = digest.finish();
+            let len = crc.byte_len();
+            match self.options.endian.resolve() {
+                Endian::Big => self.writer.write_all(&checksum.to_be_bytes()[8 - len..])?,
+                _ => self.writer.write_all(&checksum.to_le_bytes()[..len])?,
+            }
+        }
+        let resume = self.writer.stream_position()?;
+
+        self.writer.seek(SeekFrom::Start(self.start + 4))?;
+        size.write_options(self.writer, &self.options)?;
+        self.writer.seek(SeekFrom::Start(resume))?;
+        pad(self.writer, size as usize % 2)
+    }
+}
+
+impl<'w, W: Write + Seek> Write for ChunkScope<'w, W> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        let written = self.writer.write(buf)?;
+        if let Some((_, digest)) = &mut self.crc {
+            digest.update(&buf[..written]);
+        }
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.writer.flush()
+    }
+}
+
+impl<'w, W: Write + Seek> Seek for ChunkScope<'w, W> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        self.writer.seek(pos)
+    }
+}
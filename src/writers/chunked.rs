@@ -0,0 +1,74 @@
+use super::*;
+use std::fmt;
+use std::io::Error;
+
+const CHUNK_SIZE: usize = 1024;
+
+/// A [fmt::Write] adaptor which encodes formatted text as UTF-8 or UTF-16 into a fixed stack
+/// buffer and writes it out whenever it fills up, so `Display` values are written without first
+/// formatting them into a `String`.
+pub(crate) struct ChunkedText<'w, W: Write> {
+    writer: &'w mut W,
+    options: &'w WriterOption,
+    utf16: bool,
+    buf: [u8; CHUNK_SIZE],
+    len: usize,
+    error: Option<Error>,
+}
+
+impl<'w, W: Write> ChunkedText<'w, W> {
+    pub(crate) fn utf8(writer: &'w mut W, options: &'w WriterOption) -> Self {
+        ChunkedText { writer, options, utf16: false, buf: [0; CHUNK_SIZE], len: 0, error: None }
+    }
+
+    /// UTF-16 code units are written using the endianness in `options`
+    pub(crate) fn utf16(writer: &'w mut W, options: &'w WriterOption) -> Self {
+        ChunkedText { utf16: true, ..Self::utf8(writer, options) }
+    }
+
+    /// Format `value` into the writer
+    pub(crate) fn write_display<D: fmt::Display + ?Sized>(mut self, value: &D) -> Result<()> {
+        if fmt::Write::write_fmt(&mut self, format_args!("{}", value)).is_err() {
+            return Err(self.error.take().unwrap_or_else(|| Error::other("formatting failed")));
+        }
+        self.flush()
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.writer.write_all(&self.buf[..self.len])?;
+        self.len = 0;
+        Ok(())
+    }
+
+    fn push_str(&mut self, s: &str) -> Result<()> {
+        if !self.utf16 {
+            if self.len + s.len() > CHUNK_SIZE {
+                self.flush()?;
+            }
+            if s.len() > CHUNK_SIZE {
+                return self.writer.write_all(s.as_bytes());
+            }
+            self.buf[self.len..self.len + s.len()].copy_from_slice(s.as_bytes());
+            self.len += s.len();
+            return Ok(());
+        }
+
+        for unit in s.encode_utf16() {
+            if self.len + 2 > CHUNK_SIZE {
+                self.flush()?;
+            }
+            BinWrite::write_options(&unit, &mut &mut self.buf[self.len..self.len + 2], self.options)?;
+            self.len += 2;
+        }
+        Ok(())
+    }
+}
+
+impl<'w, W: Write> fmt::Write for ChunkedText<'w, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.push_str(s).map_err(|err| {
+            self.error = Some(err);
+            fmt::Error
+        })
+    }
+}
@@ -0,0 +1,55 @@
+use super::*;
+use std::convert::TryFrom;
+use std::io::{Error, ErrorKind};
+use std::marker::PhantomData;
+
+/// An enum (or other set of values) with a name for each value, for writing as a
+/// [ConstantsTable]. Implement alongside `#[derive(BinWrite)]` so the ids are written the same
+/// way as the enum itself.
+pub trait NamedConstants: BinWrite + Sized + 'static {
+    /// Every value paired with its name, in table order
+    fn constants() -> &'static [(Self, &'static str)];
+}
+
+/// A table of `(id, name offset)` entries followed by the null-terminated names. Each id is
+/// written using its BinWrite impl and each name offset as a `P`, relative to the start of the
+/// table. Useful for symbol and metadata sections listing an enum's values by name.
+pub struct ConstantsTable<'a, T, P> {
+    entries: &'a [(T, &'a str)],
+    _offset: PhantomData<P>,
+}
+
+impl<'a, T: BinWrite, P> ConstantsTable<'a, T, P> {
+    pub fn new(entries: &'a [(T, &'a str)]) -> Self {
+        ConstantsTable { entries, _offset: PhantomData }
+    }
+}
+
+impl<T: NamedConstants, P> ConstantsTable<'static, T, P> {
+    /// A table of every value of `T`
+    pub fn of() -> Self {
+        Self::new(T::constants())
+    }
+}
+
+impl<'a, T: BinWrite, P: TryFrom<usize> + BinWrite> BinWrite for ConstantsTable<'a, T, P> {
+    fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+        let offset_size: usize = byte_len_of(&len_as::<P>(0)?, options)?;
+        let mut names_start = 0;
+        for (id, _) in self.entries {
+            names_start += byte_len_of::<usize, _>(id, options)? + offset_size;
+        }
+
+        let mut names = vec![];
+        for (id, name) in self.entries {
+            if name.contains('\0') {
+                return Err(Error::new(ErrorKind::InvalidInput, format!("constant name {:?} contains a null byte", name)));
+            }
+            BinWrite::write_options(id, writer, options)?;
+            BinWrite::write_options(&len_as::<P>(names_start + names.len())?, writer, options)?;
+            names.extend_from_slice(name.as_bytes());
+            names.push(0);
+        }
+        writer.write_all(&names)
+    }
+}
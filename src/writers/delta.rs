@@ -0,0 +1,35 @@
+/// Integer types which can be delta encoded using [delta]
+pub trait DeltaEncode: Copy {
+    /// The wrapping difference `self - previous`
+    fn delta_from(self, previous: Self) -> Self;
+}
+
+macro_rules! delta_encode_impl {
+    ($($type_name:ty),*$(,)?) => {
+        $(
+            impl DeltaEncode for $type_name {
+                fn delta_from(self, previous: Self) -> Self {
+                    self.wrapping_sub(previous)
+                }
+            }
+        )*
+    }
+}
+
+delta_encode_impl!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// A preprocessor for delta encoding, which replaces each value with its difference from the
+/// previous value (the first value is kept as-is). Subtraction wraps, so decreasing values are
+/// supported for unsigned types. Use `#[binwrite(preprocessor(writers::delta))]`.
+///
+/// Readers can decode this by keeping a running (wrapping) sum of the values, starting at 0.
+pub fn delta<T: DeltaEncode + Default>(values: &[T]) -> Vec<T> {
+    let mut previous = T::default();
+    values.iter()
+        .map(|value| {
+            let delta = value.delta_from(previous);
+            previous = *value;
+            delta
+        })
+        .collect()
+}
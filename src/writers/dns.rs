@@ -0,0 +1,69 @@
+use super::*;
+use std::collections::HashMap;
+use std::io::{Error, ErrorKind};
+
+fn check_label(label: &str) -> Result<()> {
+    if label.is_empty() || label.len() > 63 {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!("DNS label {:?} must be between 1 and 63 bytes", label)
+        ));
+    }
+    Ok(())
+}
+
+/// The labels of a name, ignoring any trailing dot (the root name "." has no labels)
+fn labels(name: &str) -> impl Iterator<Item = &str> {
+    let name = name.trim_end_matches('.');
+    name.split('.').take(if name.is_empty() { 0 } else { usize::MAX })
+}
+
+/// A built in writer for DNS-style names, which writes each dot-separated label prefixed by its
+/// length followed by a zero length terminator (`example.com` is written as
+/// `\x07example\x03com\x00`). Use `#[binwrite(with(writers::dns_name))]`.
+pub fn dns_name<S: AsRef<str> + ?Sized, W: Write>(name: &S, writer: &mut W, _options: &WriterOption) -> Result<()> {
+    for label in labels(name.as_ref()) {
+        check_label(label)?;
+        writer.write_all(&[label.len() as u8])?;
+        writer.write_all(label.as_bytes())?;
+    }
+    writer.write_all(&[0])
+}
+
+/// A writer for DNS names with message compression: once a name has been written, later names
+/// ending with the same labels are written as a pointer to the earlier occurrence. Offsets are
+/// relative to the start of the message, so the compressor needs to be told the offset each name
+/// is written at.
+#[derive(Clone, Debug, Default)]
+pub struct DnsNameCompressor {
+    offsets: HashMap<String, u16>,
+}
+
+impl DnsNameCompressor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Write `name`, which is being written at `offset` within the message
+    pub fn write<W: Write>(&mut self, name: &str, offset: usize, writer: &mut W) -> Result<()> {
+        let labels: Vec<&str> = labels(name).collect();
+        let mut offset = offset;
+        for i in 0..labels.len() {
+            let suffix = labels[i..].join(".").to_ascii_lowercase();
+            if let Some(pointer) = self.offsets.get(&suffix) {
+                return writer.write_all(&(0xC000 | pointer).to_be_bytes());
+            }
+
+            let label = labels[i];
+            check_label(label)?;
+            // Pointers are 14 bits, so only names early enough in the message can be pointed to
+            if offset <= 0x3FFF {
+                self.offsets.insert(suffix, offset as u16);
+            }
+            writer.write_all(&[label.len() as u8])?;
+            writer.write_all(label.as_bytes())?;
+            offset += 1 + label.len();
+        }
+        writer.write_all(&[0])
+    }
+}
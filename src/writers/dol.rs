@@ -0,0 +1,188 @@
+//! # Example:
+//! ```rust
+//! use binwrite::BinWrite;
+//! use binwrite::writers::dol::{DolFile, DolSection};
+//!
+//! let dol = DolFile {
+//!     text: vec![DolSection { address: 0x8000_3100, data: vec![0x60, 0, 0, 0] }],
+//!     data: vec![DolSection { address: 0x8000_4000, data: vec![1; 0x10] }],
+//!     bss_address: 0x8000_4010,
+//!     bss_size: 0x100,
+//!     entry_point: 0x8000_3100,
+//! };
+//!
+//! let mut bytes = vec![];
+//! dol.write(&mut bytes).unwrap();
+//!
+//! // The first text section is placed right after the header
+//! assert_eq!(&bytes[..4], &[0, 0, 1, 0]);
+//! assert_eq!(bytes.len(), 0x100 + 0x20 + 0x20);
+//! ```
+use super::*;
+use std::io::{Error, ErrorKind};
+
+/// Size of a DOL header
+pub const DOL_HEADER_SIZE: usize = 0x100;
+/// Alignment of each section's data within a DOL file
+pub const DOL_SECTION_ALIGNMENT: usize = 0x20;
+pub const MAX_TEXT_SECTIONS: usize = 7;
+pub const MAX_DATA_SECTIONS: usize = 11;
+
+/// A section of a DOL file and the address it is loaded to
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DolSection {
+    pub address: u32,
+    pub data: Vec<u8>,
+}
+
+/// A GameCube/Wii DOL executable. Section offsets are computed when written, with each
+/// section's data aligned to 0x20 bytes after the header, text sections first. DOL is always big
+/// endian, regardless of the options.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DolFile {
+    /// Up to 7 text (code) sections
+    pub text: Vec<DolSection>,
+    /// Up to 11 data sections
+    pub data: Vec<DolSection>,
+    pub bss_address: u32,
+    pub bss_size: u32,
+    pub entry_point: u32,
+}
+
+/// The file offset, address and size of each section slot, with unused slots zeroed
+struct SectionTable<const N: usize> {
+    offsets: [u32; N],
+    addresses: [u32; N],
+    sizes: [u32; N],
+}
+
+impl<const N: usize> SectionTable<N> {
+    fn new(sections: &[DolSection], offset: &mut usize) -> Result<Self> {
+        if sections.len() > N {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("{} sections is more than the {} a DOL can hold", sections.len(), N)
+            ));
+        }
+        let mut table = SectionTable { offsets: [0; N], addresses: [0; N], sizes: [0; N] };
+        for (i, section) in sections.iter().enumerate() {
+            table.offsets[i] = len_as(*offset)?;
+            table.addresses[i] = section.address;
+            table.sizes[i] = len_as(section.data.len())?;
+            *offset += section.data.len() + padding_for(section.data.len() as u64, DOL_SECTION_ALIGNMENT)?;
+        }
+        Ok(table)
+    }
+}
+
+impl DolFile {
+    fn write_sections<W: Write>(sections: &[DolSection], writer: &mut W) -> Result<()> {
+        for section in sections {
+            writer.write_all(&section.data)?;
+            pad(writer, padding_for(section.data.len() as u64, DOL_SECTION_ALIGNMENT)?)?;
+        }
+        Ok(())
+    }
+}
+
+impl BinWrite for DolFile {
+    fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+        let options = WriterOption {
+            endian: Endian::Big,
+            ..options.clone()
+        };
+
+        let mut offset = DOL_HEADER_SIZE;
+        let text = SectionTable::<MAX_TEXT_SECTIONS>::new(&self.text, &mut offset)?;
+        let data = SectionTable::<MAX_DATA_SECTIONS>::new(&self.data, &mut offset)?;
+
+        (
+            text.offsets, data.offsets, text.addresses, data.addresses, text.sizes, data.sizes,
+            self.bss_address, self.bss_size, self.entry_point,
+        ).write_options(writer, &options)?;
+        pad(writer, DOL_HEADER_SIZE - 0xE4)?;
+
+        Self::write_sections(&self.text, writer)?;
+        Self::write_sections(&self.data, writer)
+    }
+}
+
+/// The header of a REL relocatable module. Offsets are from the start of the REL file.
+/// `version` decides how many fields are written: `align` and `bss_align` from version 2 and
+/// `fix_size` from version 3.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RelHeader {
+    pub id: u32,
+    pub num_sections: u32,
+    pub section_info_offset: u32,
+    pub name_offset: u32,
+    pub name_size: u32,
+    pub version: u32,
+    pub bss_size: u32,
+    pub rel_offset: u32,
+    pub imp_offset: u32,
+    pub imp_size: u32,
+    pub prolog_section: u8,
+    pub epilog_section: u8,
+    pub unresolved_section: u8,
+    pub prolog: u32,
+    pub epilog: u32,
+    pub unresolved: u32,
+    pub align: u32,
+    pub bss_align: u32,
+    pub fix_size: u32,
+}
+
+impl RelHeader {
+    /// The size of the header for this header's version, which is where the section info table
+    /// usually starts
+    pub fn size(&self) -> usize {
+        match self.version {
+            0 | 1 => 0x40,
+            2 => 0x48,
+            _ => 0x4C,
+        }
+    }
+}
+
+impl BinWrite for RelHeader {
+    fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+        let options = WriterOption {
+            endian: Endian::Big,
+            ..options.clone()
+        };
+        // The next and previous module links are filled in at runtime
+        (
+            self.id, 0u32, 0u32, self.num_sections, self.section_info_offset, self.name_offset,
+            self.name_size, self.version, self.bss_size, self.rel_offset, self.imp_offset,
+            self.imp_size, self.prolog_section, self.epilog_section, self.unresolved_section, 0u8,
+            self.prolog, self.epilog, self.unresolved,
+        ).write_options(writer, &options)?;
+        if self.version >= 2 {
+            (self.align, self.bss_align).write_options(writer, &options)?;
+        }
+        if self.version >= 3 {
+            self.fix_size.write_options(writer, &options)?;
+        }
+        Ok(())
+    }
+}
+
+/// An entry in a REL's section info table
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RelSectionInfo {
+    /// Offset of the section's data, or 0 for BSS and unused sections
+    pub offset: u32,
+    pub size: u32,
+    pub executable: bool,
+}
+
+impl BinWrite for RelSectionInfo {
+    fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+        let options = WriterOption {
+            endian: Endian::Big,
+            ..options.clone()
+        };
+        (self.offset | self.executable as u32, self.size).write_options(writer, &options)
+    }
+}
@@ -0,0 +1,116 @@
+//! # Example:
+//! ```rust
+//! use binwrite::{BinWrite, WriterOption};
+//! use binwrite::writers::elf::SectionLayout;
+//!
+//! let options = WriterOption::default();
+//!
+//! // Sections start after a 0x40 byte ELF header
+//! let mut layout = SectionLayout::new(0x40);
+//! layout.add(".text", &[0x90u8; 6], 0x10, None, &options).unwrap();
+//! // A loadable segment whose file offset must be congruent to its address modulo the page size
+//! layout.add(".data", &1u32, 0x1000, Some(0x0040_2008), &options).unwrap();
+//!
+//! let sections = layout.placements();
+//! assert_eq!((sections[0].offset, sections[0].size), (0x40, 6));
+//! assert_eq!((sections[1].offset, sections[1].size), (0x1008, 4));
+//! assert_eq!(layout.end(), 0x100C);
+//!
+//! // Write the header table from the placements, then the sections themselves
+//! let mut bytes = vec![];
+//! for section in &sections {
+//!     (section.offset, section.size).write_options(&mut bytes, &options).unwrap();
+//! }
+//! bytes.resize(0x40, 0);
+//! layout.write(&mut bytes).unwrap();
+//! assert_eq!(bytes.len() as u64, layout.end());
+//! ```
+use super::*;
+use std::io::{Error, ErrorKind};
+
+/// Where a section was placed by a [SectionLayout]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PlacedSection {
+    pub name: String,
+    /// Offset of the section from the start of the file
+    pub offset: u64,
+    pub size: u64,
+    pub alignment: u64,
+}
+
+struct Section {
+    name: String,
+    data: Vec<u8>,
+    offset: u64,
+    alignment: u64,
+}
+
+/// A layout of ELF-style sections, each aligned within the file. Sections are placed in the
+/// order they are added, starting at `start` (usually just after the file header and program
+/// header table), so their offsets and sizes are known before anything is written and can be
+/// recorded in header tables. Writing the layout writes each section with zeroed padding and
+/// assumes the writer is already at `start`.
+pub struct SectionLayout {
+    start: u64,
+    end: u64,
+    sections: Vec<Section>,
+}
+
+impl SectionLayout {
+    pub fn new(start: u64) -> Self {
+        SectionLayout { start, end: start, sections: Vec::new() }
+    }
+
+    /// Add a section aligned to `alignment`, returning its offset. If `address` is given, the
+    /// section is instead placed at an offset congruent to the address modulo the alignment, as
+    /// loadable segments require.
+    pub fn add<T: BinWrite + ?Sized>(
+        &mut self,
+        name: &str,
+        value: &T,
+        alignment: u64,
+        address: Option<u64>,
+        options: &WriterOption,
+    ) -> Result<u64> {
+        if alignment == 0 {
+            return Err(Error::new(ErrorKind::InvalidInput, format!("alignment of {} is 0", name)));
+        }
+        let mut data = vec![];
+        BinWrite::write_options(value, &mut data, options)?;
+
+        let target = address.unwrap_or(0) % alignment;
+        let offset = self.end + (target + alignment - self.end % alignment) % alignment;
+        self.end = offset + data.len() as u64;
+        self.sections.push(Section { name: name.to_owned(), data, offset, alignment });
+        Ok(offset)
+    }
+
+    /// The placement of every section, in the order they were added
+    pub fn placements(&self) -> Vec<PlacedSection> {
+        self.sections.iter()
+            .map(|section| PlacedSection {
+                name: section.name.clone(),
+                offset: section.offset,
+                size: section.data.len() as u64,
+                alignment: section.alignment,
+            })
+            .collect()
+    }
+
+    /// The offset just past the last section
+    pub fn end(&self) -> u64 {
+        self.end
+    }
+}
+
+impl BinWrite for SectionLayout {
+    fn write_options<W: Write>(&self, writer: &mut W, _options: &WriterOption) -> Result<()> {
+        let mut pos = self.start;
+        for section in &self.sections {
+            pad(writer, (section.offset - pos) as usize)?;
+            writer.write_all(&section.data)?;
+            pos = section.offset + section.data.len() as u64;
+        }
+        Ok(())
+    }
+}
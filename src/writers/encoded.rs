@@ -0,0 +1,164 @@
+use super::*;
+use encoding_rs::{EncoderResult, Encoding};
+use std::io::{Error, ErrorKind};
+
+/// What [FixedEncoded] does when an encoded string is too long for its buffer
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Overflow {
+    /// Drop the characters that don't fit, never splitting a multi-byte character
+    Truncate,
+    /// Return an error
+    Error,
+}
+
+/// A wrapper which encodes a string to a legacy encoding (such as
+/// [SHIFT_JIS](encoding_rs::SHIFT_JIS)) in a fixed buffer of `size` bytes, padding the remainder
+/// with `fill`. Characters the encoding can't represent are an error. Use
+/// `#[binwrite(preprocessor(fixed_encoded(SHIFT_JIS, 0x20, Overflow::Truncate, 0)))]` to write a
+/// 32-byte Shift-JIS name field.
+///
+/// Note: as with [Encoding::new_encoder], UTF-16 encodings are written as UTF-8.
+pub struct FixedEncoded<'a, S: ?Sized> {
+    string: &'a S,
+    encoding: &'static Encoding,
+    size: usize,
+    overflow: Overflow,
+    fill: u8,
+}
+
+impl<'a, S: ?Sized> FixedEncoded<'a, S> {
+    pub fn new(string: &'a S, encoding: &'static Encoding, size: usize, overflow: Overflow, fill: u8) -> Self {
+        FixedEncoded { string, encoding, size, overflow, fill }
+    }
+}
+
+impl<'a, S: AsRef<str> + ?Sized> FixedEncoded<'a, S> {
+    /// Encode the string, returning at most `size` bytes
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        let string = self.string.as_ref();
+        let mut encoder = self.encoding.new_encoder();
+        let mut buf = [0u8; 16];
+        let mut chars = [0u8; 4];
+
+        // Find the longest prefix whose characters fit, not counting the reset sequence a
+        // stateful encoding (such as ISO-2022-JP) needs to switch back to its initial state
+        let mut len = 0;
+        let mut end = string.len();
+        for (i, c) in string.char_indices() {
+            let c = c.encode_utf8(&mut chars);
+            let (result, _, written) = encoder.encode_from_utf8_without_replacement(c, &mut buf, false);
+            if let EncoderResult::Unmappable(c) = result {
+                return Err(self.unmappable(c));
+            }
+            if len + written > self.size {
+                end = i;
+                break;
+            }
+            len += written;
+        }
+        if end < string.len() && self.overflow == Overflow::Error {
+            return Err(self.too_long(string));
+        }
+
+        // Including the reset sequence may take more room, so drop characters until it fits
+        let mut boundaries = string[..end].char_indices().map(|(i, _)| i).rev();
+        let mut prefix = &string[..end];
+        loop {
+            let bytes = self.encode_all(prefix)?;
+            if bytes.len() <= self.size {
+                return Ok(bytes);
+            }
+            match (self.overflow, boundaries.next()) {
+                (Overflow::Truncate, Some(i)) => prefix = &string[..i],
+                _ => return Err(self.too_long(string)),
+            }
+        }
+    }
+
+    /// Encode all of `string` with a fresh encoder, including any reset sequence
+    fn encode_all(&self, string: &str) -> Result<Vec<u8>> {
+        let mut encoder = self.encoding.new_encoder();
+        let len = encoder.max_buffer_length_from_utf8_without_replacement(string.len())
+            .ok_or_else(|| self.too_long(string))?;
+        let mut bytes = vec![0u8; len];
+        let (result, _, written) = encoder.encode_from_utf8_without_replacement(string, &mut bytes, true);
+        if let EncoderResult::Unmappable(c) = result {
+            return Err(self.unmappable(c));
+        }
+        bytes.truncate(written);
+        Ok(bytes)
+    }
+
+    fn unmappable(&self, c: char) -> Error {
+        Error::new(ErrorKind::InvalidData, format!("{:?} can't be encoded as {}", c, self.encoding.name()))
+    }
+
+    fn too_long(&self, string: &str) -> Error {
+        Error::new(
+            ErrorKind::InvalidData,
+            format!("{:?} does not fit in {} bytes of {}", string, self.size, self.encoding.name())
+        )
+    }
+}
+
+impl<'a, S: AsRef<str> + ?Sized> BinWrite for FixedEncoded<'a, S> {
+    fn write_options<W: Write>(&self, writer: &mut W, _options: &WriterOption) -> Result<()> {
+        let bytes = self.encode()?;
+        writer.write_all(&bytes)?;
+        pad_with(writer, self.size - bytes.len(), self.fill)
+    }
+}
+
+/// A preprocessor for writing a string in a legacy encoding to a fixed-size buffer. See
+/// [FixedEncoded].
+pub fn fixed_encoded<S: AsRef<str> + ?Sized>(
+    encoding: &'static Encoding,
+    size: usize,
+    overflow: Overflow,
+    fill: u8,
+) -> impl Fn(&S) -> FixedEncoded<'_, S> {
+    move |string| FixedEncoded::new(string, encoding, size, overflow, fill)
+}
+
+/// A wrapper which encodes a string to a legacy encoding through a fixed stack buffer, writing
+/// each chunk as it fills so memory use stays flat for very large strings. Characters the
+/// encoding can't represent are an error, which may come after earlier chunks were written. Use
+/// `#[binwrite(preprocessor(encoded(SHIFT_JIS)))]`.
+///
+/// Note: as with [Encoding::new_encoder], UTF-16 encodings are written as UTF-8.
+pub struct Encoded<'a, S: ?Sized> {
+    string: &'a S,
+    encoding: &'static Encoding,
+}
+
+impl<'a, S: ?Sized> Encoded<'a, S> {
+    pub fn new(string: &'a S, encoding: &'static Encoding) -> Self {
+        Encoded { string, encoding }
+    }
+}
+
+impl<'a, S: AsRef<str> + ?Sized> BinWrite for Encoded<'a, S> {
+    fn write_options<W: Write>(&self, writer: &mut W, _options: &WriterOption) -> Result<()> {
+        let mut encoder = self.encoding.new_encoder();
+        let mut buf = [0u8; 1024];
+        let mut remaining = self.string.as_ref();
+        loop {
+            let (result, read, written) = encoder.encode_from_utf8_without_replacement(remaining, &mut buf, true);
+            writer.write_all(&buf[..written])?;
+            remaining = &remaining[read..];
+            match result {
+                EncoderResult::InputEmpty => return Ok(()),
+                EncoderResult::OutputFull => continue,
+                EncoderResult::Unmappable(c) => return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("{:?} can't be encoded as {}", c, self.encoding.name())
+                )),
+            }
+        }
+    }
+}
+
+/// A preprocessor for streaming a string in a legacy encoding. See [Encoded].
+pub fn encoded<S: AsRef<str> + ?Sized>(encoding: &'static Encoding) -> impl Fn(&S) -> Encoded<'_, S> {
+    move |string| Encoded::new(string, encoding)
+}
@@ -0,0 +1,116 @@
+use super::*;
+use crate::scratch::ScratchBuffer;
+use std::io::{Error, ErrorKind};
+
+fn missing_key() -> Error {
+    Error::new(ErrorKind::InvalidInput, "no encryption key in WriterOption::key")
+}
+
+/// A chained XOR cipher: each byte is XORed with a repeating key and with the previous encrypted
+/// byte, starting from an initialization vector. Chaining means identical records encrypt
+/// differently when they are given different IVs.
+#[derive(Clone, Debug)]
+pub struct XorChain {
+    key: Vec<u8>,
+    pos: usize,
+    prev: u8,
+}
+
+impl XorChain {
+    /// Create a cipher, returning an error if the key is empty
+    pub fn new(key: &[u8], iv: u8) -> Result<Self> {
+        if key.is_empty() {
+            return Err(Error::new(ErrorKind::InvalidInput, "encryption key is empty"));
+        }
+        Ok(XorChain { key: key.to_vec(), pos: 0, prev: iv })
+    }
+
+    /// Encrypt `data` in place, continuing from any data encrypted before it
+    pub fn encrypt(&mut self, data: &mut [u8]) {
+        for byte in data {
+            *byte ^= self.key[self.pos % self.key.len()] ^ self.prev;
+            self.prev = *byte;
+            self.pos += 1;
+        }
+    }
+
+    /// The last encrypted byte, or the IV if nothing has been encrypted
+    pub fn last(&self) -> u8 {
+        self.prev
+    }
+}
+
+impl StreamProcessor for XorChain {
+    fn process<W: Write>(&mut self, data: &[u8], writer: &mut W) -> Result<()> {
+        let mut data = data.to_vec();
+        self.encrypt(&mut data);
+        writer.write_all(&data)
+    }
+
+    fn finish<W: Write>(self, _writer: &mut W, _options: &WriterOption) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// A wrapper which encrypts the inner value with a [XorChain] using the key from
+/// [WriterOption::key](crate::WriterOption::key) and the given IV. Writing returns an error if
+/// there is no key. Use `#[binwrite(preprocessor(encrypted(0x5A)))]` to encrypt a field.
+pub struct Encrypted<'a, T: ?Sized> {
+    value: &'a T,
+    iv: u8,
+}
+
+impl<'a, T: ?Sized> Encrypted<'a, T> {
+    pub fn new(value: &'a T, iv: u8) -> Self {
+        Encrypted { value, iv }
+    }
+}
+
+impl<'a, T: BinWrite + ?Sized> BinWrite for Encrypted<'a, T> {
+    fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+        let key = options.key.as_ref().ok_or_else(missing_key)?;
+        let mut writer = ProcessingWriter::new(writer, XorChain::new(key, self.iv)?);
+        BinWrite::write_options(self.value, &mut writer, options)?;
+        writer.finish(options)
+    }
+}
+
+/// A preprocessor for encrypting a field with the key from the options. See [Encrypted].
+pub fn encrypted<T: BinWrite + ?Sized>(iv: u8) -> impl Fn(&T) -> Encrypted<'_, T> {
+    move |value| Encrypted::new(value, iv)
+}
+
+/// A writer for formats which encrypt each record independently with a chained IV. Each record
+/// is encrypted with a [XorChain] using the key from
+/// [WriterOption::key](crate::WriterOption::key), restarting the key for every record, and the
+/// last encrypted byte of each record becomes the IV of the next.
+pub struct RecordEncryptor {
+    iv: u8,
+}
+
+impl RecordEncryptor {
+    /// Create an encryptor whose first record uses `iv`
+    pub fn new(iv: u8) -> Self {
+        RecordEncryptor { iv }
+    }
+
+    /// The IV the next record will be encrypted with
+    pub fn iv(&self) -> u8 {
+        self.iv
+    }
+
+    /// Encrypt and write a single record
+    pub fn write<T, W>(&mut self, record: &T, writer: &mut W, options: &WriterOption) -> Result<()>
+        where T: BinWrite + ?Sized,
+              W: Write,
+    {
+        let key = options.key.as_ref().ok_or_else(missing_key)?;
+        let mut cipher = XorChain::new(key, self.iv)?;
+        let mut bytes = ScratchBuffer::new(options);
+        BinWrite::write_options(record, &mut *bytes, options)?;
+        cipher.encrypt(&mut bytes);
+        writer.write_all(&bytes)?;
+        self.iv = cipher.last();
+        Ok(())
+    }
+}
@@ -0,0 +1,31 @@
+use super::*;
+
+/// A wrapper which only writes the inner value when writing with the given endianness, for
+/// fields that only exist in one endianness variant of a format (such as padding words on big
+/// endian platforms). [Native](Endian::Native) is resolved to the target's endianness before
+/// comparing. Use `#[binwrite(preprocessor(only_if_endian(Endian::Big)))]`.
+pub struct OnlyIfEndian<'a, T: ?Sized> {
+    value: &'a T,
+    endian: Endian,
+}
+
+impl<'a, T: ?Sized> OnlyIfEndian<'a, T> {
+    pub fn new(value: &'a T, endian: Endian) -> Self {
+        OnlyIfEndian { value, endian }
+    }
+}
+
+impl<'a, T: BinWrite + ?Sized> BinWrite for OnlyIfEndian<'a, T> {
+    fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+        if options.endian.resolve() == self.endian.resolve() {
+            BinWrite::write_options(self.value, writer, options)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// A preprocessor for writing a field only with the given endianness. See [OnlyIfEndian].
+pub fn only_if_endian<T: BinWrite + ?Sized>(endian: Endian) -> impl Fn(&T) -> OnlyIfEndian<'_, T> {
+    move |value| OnlyIfEndian::new(value, endian)
+}
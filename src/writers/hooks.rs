@@ -0,0 +1,54 @@
+use super::*;
+use crate::scratch::ScratchBuffer;
+
+/// A wrapper which writes the inner value into a buffer and then runs a finalize hook on it
+/// before the buffer is written out. The hook receives the value, the bytes written so far and
+/// the options, and can append (indices, footers, EOF markers) or backfill earlier bytes. Use
+/// `#[binwrite(preprocessor(finalized(my_hook)))]`.
+pub struct Finalized<'a, T: ?Sized, F> {
+    value: &'a T,
+    hook: F,
+}
+
+impl<'a, T, F> Finalized<'a, T, F>
+    where T: BinWrite + ?Sized,
+          F: Fn(&T, &mut Vec<u8>, &WriterOption) -> Result<()>,
+{
+    pub fn new(value: &'a T, hook: F) -> Self {
+        Finalized { value, hook }
+    }
+}
+
+impl<'a, T, F> BinWrite for Finalized<'a, T, F>
+    where T: BinWrite + ?Sized,
+          F: Fn(&T, &mut Vec<u8>, &WriterOption) -> Result<()>,
+{
+    fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+        let mut bytes = ScratchBuffer::new(options);
+        BinWrite::write_options(self.value, &mut *bytes, options)?;
+        (self.hook)(self.value, &mut bytes, options)?;
+        writer.write_all(&bytes)
+    }
+}
+
+/// A preprocessor for running a finalize hook after the value is written. See [Finalized].
+pub fn finalized<T, F>(hook: F) -> impl Fn(&T) -> Finalized<'_, T, F>
+    where T: BinWrite + ?Sized,
+          F: Fn(&T, &mut Vec<u8>, &WriterOption) -> Result<()> + Clone,
+{
+    move |value| Finalized::new(value, hook.clone())
+}
+
+/// A preprocessor which clones the value and runs a prepare hook on the clone before it is
+/// written, for normalizing a value once (sorting entries, recomputing cached fields) instead of
+/// in every field's preprocessor. Use `#[binwrite(preprocessor(prepared(my_hook)))]`.
+pub fn prepared<T, F>(hook: F) -> impl Fn(&T) -> T
+    where T: BinWrite + Clone,
+          F: Fn(&mut T),
+{
+    move |value| {
+        let mut value = value.clone();
+        hook(&mut value);
+        value
+    }
+}
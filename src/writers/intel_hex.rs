@@ -0,0 +1,105 @@
+//! # Example:
+//! ```rust
+//! use binwrite::BinWrite;
+//! use binwrite::writers::intel_hex::IntelHex;
+//!
+//! let mut bytes = vec![];
+//! IntelHex::from_bytes(0x0100, &[0x21, 0x46, 0x01], 16).write(&mut bytes).unwrap();
+//!
+//! assert_eq!(bytes, b":0301000021460194\n:00000001FF\n");
+//! ```
+use super::*;
+use std::io::{Error, ErrorKind};
+
+/// A single line of an Intel HEX file
+#[derive(Clone, Debug, PartialEq)]
+pub enum IntelHexRecord {
+    /// Data at the given address (added to the current extended address)
+    Data { address: u16, data: Vec<u8> },
+    EndOfFile,
+    /// Sets the upper 16 bits of the address of following data records
+    ExtendedLinearAddress(u16),
+    /// The address execution starts at
+    StartLinearAddress(u32),
+}
+
+pub(crate) fn write_hex_bytes<W: Write>(bytes: &[u8], writer: &mut W, options: &WriterOption) -> Result<()> {
+    for byte in bytes {
+        AsciiNumber::new(byte, Radix::UpperHex, 2, b'0').write_options(writer, options)?;
+    }
+    Ok(())
+}
+
+impl BinWrite for IntelHexRecord {
+    fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+        let (upper, start);
+        let (address, kind, data): (u16, u8, &[u8]) = match self {
+            IntelHexRecord::Data { address, data } => (*address, 0, data),
+            IntelHexRecord::EndOfFile => (0, 1, &[]),
+            IntelHexRecord::ExtendedLinearAddress(address) => {
+                upper = address.to_be_bytes();
+                (0, 4, &upper)
+            }
+            IntelHexRecord::StartLinearAddress(address) => {
+                start = address.to_be_bytes();
+                (0, 5, &start)
+            }
+        };
+        if data.len() > 0xFF {
+            return Err(Error::new(ErrorKind::InvalidData, "Intel HEX records can hold at most 255 bytes"));
+        }
+
+        let mut record = vec![data.len() as u8];
+        record.extend_from_slice(&address.to_be_bytes());
+        record.push(kind);
+        record.extend_from_slice(data);
+        let checksum = record.iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte)).wrapping_neg();
+        record.push(checksum);
+
+        writer.write_all(b":")?;
+        write_hex_bytes(&record, writer, options)?;
+        writer.write_all(b"\n")
+    }
+}
+
+/// An Intel HEX file, made up of a list of records
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct IntelHex {
+    pub records: Vec<IntelHexRecord>,
+}
+
+impl IntelHex {
+    /// Create the records for `data` loaded at `base_address`, with at most `bytes_per_line` bytes
+    /// per data record. Extended linear address records are inserted whenever the data crosses a
+    /// 64 KiB boundary, and the file is ended with an end of file record.
+    pub fn from_bytes(base_address: u32, data: &[u8], bytes_per_line: usize) -> Self {
+        let bytes_per_line = bytes_per_line.clamp(1, 0xFF);
+        let mut records = vec![];
+        let mut upper = 0u16;
+        let mut offset = 0;
+        while offset < data.len() {
+            let address = base_address.wrapping_add(offset as u32);
+            if (address >> 16) as u16 != upper {
+                upper = (address >> 16) as u16;
+                records.push(IntelHexRecord::ExtendedLinearAddress(upper));
+            }
+            // Don't let a single record cross into the next 64 KiB
+            let until_boundary = 0x1_0000 - (address & 0xFFFF) as usize;
+            let len = bytes_per_line.min(until_boundary).min(data.len() - offset);
+            records.push(IntelHexRecord::Data {
+                address: address as u16,
+                data: data[offset..offset + len].to_vec(),
+            });
+            offset += len;
+        }
+        records.push(IntelHexRecord::EndOfFile);
+
+        IntelHex { records }
+    }
+}
+
+impl BinWrite for IntelHex {
+    fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+        BinWrite::write_options(&self.records, writer, options)
+    }
+}
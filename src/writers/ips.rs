@@ -0,0 +1,124 @@
+//! # Example:
+//! ```rust
+//! use binwrite::BinWrite;
+//! use binwrite::writers::ips::IpsPatch;
+//!
+//! let original = [0u8, 1, 2, 3];
+//! let modified = [0u8, 9, 9, 3, 4];
+//!
+//! let mut bytes = vec![];
+//! IpsPatch::from_diff(&original, &modified).write(&mut bytes).unwrap();
+//!
+//! assert_eq!(bytes, b"PATCH\x00\x00\x01\x00\x02\x09\x09\x00\x00\x04\x00\x01\x04EOF");
+//! ```
+use super::*;
+use std::io::{Error, ErrorKind};
+
+const EOF_OFFSET: u32 = 0x454F46;
+const MAX_RECORD_LEN: usize = 0xFFFF;
+
+/// A single change made by an IPS patch
+#[derive(Clone, Debug, PartialEq)]
+pub enum IpsRecord {
+    /// Write `data` at `offset`
+    Data { offset: u32, data: Vec<u8> },
+    /// Write `value` `len` times at `offset`
+    Rle { offset: u32, len: u16, value: u8 },
+}
+
+/// An IPS patch, made up of records to apply to the original file and an optional size to
+/// truncate the output to.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct IpsPatch {
+    pub records: Vec<IpsRecord>,
+    pub truncate: Option<u32>,
+}
+
+impl IpsPatch {
+    /// Create a patch which turns `original` into `modified`
+    pub fn from_diff(original: &[u8], modified: &[u8]) -> Self {
+        let mut records = vec![];
+        let mut i = 0;
+        while i < modified.len() {
+            if original.get(i) == Some(&modified[i]) {
+                i += 1;
+                continue;
+            }
+
+            let mut start = i;
+            while i < modified.len() && original.get(i) != Some(&modified[i]) {
+                i += 1;
+            }
+            // An offset of "EOF" would be read as the end of the patch, so start a byte earlier
+            if start as u32 == EOF_OFFSET {
+                start -= 1;
+            }
+
+            for (chunk_index, chunk) in modified[start..i].chunks(MAX_RECORD_LEN).enumerate() {
+                let offset = (start + chunk_index * MAX_RECORD_LEN) as u32;
+                if chunk.len() > 3 && chunk.iter().all(|byte| *byte == chunk[0]) {
+                    records.push(IpsRecord::Rle { offset, len: chunk.len() as u16, value: chunk[0] });
+                } else {
+                    records.push(IpsRecord::Data { offset, data: chunk.to_vec() });
+                }
+            }
+        }
+
+        let truncate = if modified.len() < original.len() {
+            Some(modified.len() as u32)
+        } else {
+            None
+        };
+
+        IpsPatch { records, truncate }
+    }
+}
+
+fn write_u24<W: Write>(value: u32, writer: &mut W) -> Result<()> {
+    if value > 0xFF_FFFF {
+        return Err(Error::new(ErrorKind::InvalidData, format!("{:#x} does not fit in an IPS offset", value)));
+    }
+    writer.write_all(&value.to_be_bytes()[1..])
+}
+
+impl BinWrite for IpsRecord {
+    fn write_options<W: Write>(&self, writer: &mut W, _options: &WriterOption) -> Result<()> {
+        let offset = match self {
+            IpsRecord::Data { offset, .. } | IpsRecord::Rle { offset, .. } => *offset,
+        };
+        if offset == EOF_OFFSET {
+            return Err(Error::new(ErrorKind::InvalidData, "IPS records cannot start at offset 0x454F46"));
+        }
+        write_u24(offset, writer)?;
+
+        match self {
+            IpsRecord::Data { data, .. } => {
+                if data.is_empty() || data.len() > MAX_RECORD_LEN {
+                    return Err(Error::new(
+                        ErrorKind::InvalidData,
+                        format!("IPS record length {:#x} must be between 1 and 0xFFFF", data.len())
+                    ));
+                }
+                writer.write_all(&(data.len() as u16).to_be_bytes())?;
+                writer.write_all(data)
+            }
+            IpsRecord::Rle { len, value, .. } => {
+                writer.write_all(&[0, 0])?;
+                writer.write_all(&len.to_be_bytes())?;
+                writer.write_all(&[*value])
+            }
+        }
+    }
+}
+
+impl BinWrite for IpsPatch {
+    fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+        writer.write_all(b"PATCH")?;
+        BinWrite::write_options(&self.records, writer, options)?;
+        writer.write_all(b"EOF")?;
+        if let Some(truncate) = self.truncate {
+            write_u24(truncate, writer)?;
+        }
+        Ok(())
+    }
+}
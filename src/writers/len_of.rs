@@ -0,0 +1,33 @@
+use super::*;
+use std::convert::TryFrom;
+use std::io::{sink, Error, ErrorKind};
+use crate::write_track::WriteTrack;
+
+/// Convert a length to the type of a length field, returning an error if it doesn't fit
+pub(crate) fn len_as<P: TryFrom<usize>>(len: usize) -> Result<P> {
+    P::try_from(len)
+        .map_err(|_| Error::new(ErrorKind::InvalidData, format!("length {:#x} does not fit in length type", len)))
+}
+
+/// The number of items in a collection as a `P`, for length fields that are stored separately
+/// from the collection they describe. As it only depends on the value, the length field can be
+/// written before or after the collection.
+pub fn count_of<P, C, T>(items: &C) -> Result<P>
+    where P: TryFrom<usize>,
+          C: AsRef<[T]> + ?Sized,
+{
+    len_as(items.as_ref().len())
+}
+
+/// The number of bytes `value` writes with the given options as a `P`, for byte length fields
+/// that are stored separately from the data they describe. This writes the value to a
+/// [sink](std::io::sink) to measure it, so the length field can be written before or after the
+/// data.
+pub fn byte_len_of<P, T>(value: &T, options: &WriterOption) -> Result<P>
+    where P: TryFrom<usize>,
+          T: BinWrite + ?Sized,
+{
+    let mut writer = WriteTrack::new(sink());
+    BinWrite::write_options(value, &mut writer, options)?;
+    len_as(writer.pos())
+}
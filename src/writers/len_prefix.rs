@@ -0,0 +1,130 @@
+use super::*;
+use std::convert::TryFrom;
+use std::marker::PhantomData;
+
+/// A description of how a collection should be laid out. Implemented by [Items] and
+/// [LenPrefix] so that layouts can be nested to describe each level of a collection.
+pub trait CollectionLayout<T: ?Sized> {
+    fn write_layout<W: Write>(value: &T, writer: &mut W, options: &WriterOption) -> Result<()>;
+}
+
+/// The innermost level of a [LenPrefix] layout, writes the value as-is using its BinWrite
+/// implementation.
+pub struct Items;
+
+/// A layout which writes the number of items in a collection as a `P` followed by each item, with
+/// each item being laid out using `Inner`. Use `#[binwrite(with(LenPrefix::<u32>::write))]` to
+/// write a collection with a u32 length prefix.
+///
+/// Layouts can be nested for collections of collections, so
+/// `LenPrefix::<u32, LenPrefix<u16>>::write` writes a `Vec<Vec<T>>` as a u32 outer count followed
+/// by a u16 count and the items for each inner Vec.
+pub struct LenPrefix<P, Inner = Items>(PhantomData<(P, Inner)>);
+
+impl<P, Inner> LenPrefix<P, Inner> {
+    /// Write a collection using this layout
+    pub fn write<T, W>(value: &T, writer: &mut W, options: &WriterOption) -> Result<()>
+        where T: ?Sized,
+              W: Write,
+              Self: CollectionLayout<T>,
+    {
+        <Self as CollectionLayout<T>>::write_layout(value, writer, options)
+    }
+}
+
+impl<T: BinWrite + ?Sized> CollectionLayout<T> for Items {
+    fn write_layout<W: Write>(value: &T, writer: &mut W, options: &WriterOption) -> Result<()> {
+        BinWrite::write_options(value, writer, options)
+    }
+}
+
+impl<T, P, Inner> CollectionLayout<[T]> for LenPrefix<P, Inner>
+    where P: TryFrom<usize> + BinWrite,
+          Inner: CollectionLayout<T>,
+{
+    fn write_layout<W: Write>(items: &[T], writer: &mut W, options: &WriterOption) -> Result<()> {
+        let len: P = len_of::len_as(items.len())?;
+        BinWrite::write_options(&len, writer, options)?;
+        for item in items {
+            Inner::write_layout(item, writer, options)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T, P, Inner> CollectionLayout<Vec<T>> for LenPrefix<P, Inner>
+    where Self: CollectionLayout<[T]>,
+{
+    fn write_layout<W: Write>(items: &Vec<T>, writer: &mut W, options: &WriterOption) -> Result<()> {
+        <Self as CollectionLayout<[T]>>::write_layout(&items[..], writer, options)
+    }
+}
+
+/// A unit that a string length prefix can count in, see [StrLenPrefix].
+pub trait StrLenUnit {
+    fn str_len(string: &str) -> usize;
+}
+
+/// Count the length of a string in UTF-8 bytes
+pub struct Utf8Bytes;
+
+/// Count the length of a string in UTF-16 code units, as Java and C# `char` counts do
+pub struct Utf16Units;
+
+/// Count the length of a string in Unicode scalar values
+pub struct Chars;
+
+impl StrLenUnit for Utf8Bytes {
+    fn str_len(string: &str) -> usize {
+        string.len()
+    }
+}
+
+impl StrLenUnit for Utf16Units {
+    fn str_len(string: &str) -> usize {
+        string.encode_utf16().count()
+    }
+}
+
+impl StrLenUnit for Chars {
+    fn str_len(string: &str) -> usize {
+        string.chars().count()
+    }
+}
+
+/// A string layout which writes the length of the string as a `P` counted in `Unit`s, followed
+/// by the string itself. Use `#[binwrite(with(StrLenPrefix::<u16, Utf16Units>::write))]` for
+/// formats which prefix a UTF-8 string by its `char` count, or
+/// `#[binwrite(with(StrLenPrefix::<u16, Utf16Units>::write_utf16))]` for UTF-16 strings.
+pub struct StrLenPrefix<P, Unit = Utf8Bytes>(PhantomData<(P, Unit)>);
+
+impl<P, Unit> StrLenPrefix<P, Unit>
+    where P: TryFrom<usize> + BinWrite,
+          Unit: StrLenUnit,
+{
+    fn write_len<W: Write>(string: &str, writer: &mut W, options: &WriterOption) -> Result<()> {
+        let len: P = len_of::len_as(Unit::str_len(string))?;
+        BinWrite::write_options(&len, writer, options)
+    }
+
+    /// Write the length prefix followed by the string as UTF-8
+    pub fn write<S, W>(string: &S, writer: &mut W, options: &WriterOption) -> Result<()>
+        where S: AsRef<str> + ?Sized,
+              W: Write,
+    {
+        let string = string.as_ref();
+        Self::write_len(string, writer, options)?;
+        writer.write_all(string.as_bytes())
+    }
+
+    /// Write the length prefix followed by the string as UTF-16, using the endianness for byte
+    /// order
+    pub fn write_utf16<S, W>(string: &S, writer: &mut W, options: &WriterOption) -> Result<()>
+        where S: AsRef<str> + ?Sized,
+              W: Write,
+    {
+        let string = string.as_ref();
+        Self::write_len(string, writer, options)?;
+        utf16_string(string, writer, options)
+    }
+}
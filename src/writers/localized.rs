@@ -0,0 +1,92 @@
+use super::*;
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::marker::PhantomData;
+
+/// How the strings of a [LocalizedStrings] table are encoded
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TextEncoding {
+    Utf8,
+    /// UTF-16 using the endianness for byte order
+    Utf16,
+}
+
+/// A localization block mapping locales to strings, written in the common layout of a `P` count,
+/// then a `(locale, offset)` entry per locale with each offset as a `P`, then the pool of encoded
+/// strings. Offsets are relative to the start of the block. Identical strings are stored once in
+/// the pool. Locales are written in sorted order using their BinWrite impl, so they can be codes
+/// (`[u8; 4]`, such as `*b"enUS"`) or numeric ids.
+///
+/// ```rust
+/// use std::collections::BTreeMap;
+/// use binwrite::BinWrite;
+/// use binwrite::writers::{LocalizedStrings, TextEncoding};
+///
+/// let mut names = BTreeMap::new();
+/// names.insert(*b"en", "Sword");
+/// names.insert(*b"fr", "Epee");
+///
+/// let mut bytes = vec![];
+/// LocalizedStrings::<_, _, u8>::new(&names, TextEncoding::Utf8, true).write(&mut bytes).unwrap();
+///
+/// assert_eq!(bytes, b"\x02en\x07fr\x0dSword\0Epee\0");
+/// ```
+pub struct LocalizedStrings<'a, K, S, P> {
+    strings: &'a BTreeMap<K, S>,
+    encoding: TextEncoding,
+    null_terminated: bool,
+    _offset: PhantomData<P>,
+}
+
+impl<'a, K, S, P> LocalizedStrings<'a, K, S, P> {
+    pub fn new(strings: &'a BTreeMap<K, S>, encoding: TextEncoding, null_terminated: bool) -> Self {
+        LocalizedStrings { strings, encoding, null_terminated, _offset: PhantomData }
+    }
+}
+
+impl<'a, K, S, P> BinWrite for LocalizedStrings<'a, K, S, P>
+    where K: BinWrite,
+          S: AsRef<str>,
+          P: TryFrom<usize> + BinWrite,
+{
+    fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+        let alignment = match self.encoding {
+            TextEncoding::Utf8 => 1,
+            TextEncoding::Utf16 => 2,
+        };
+        let mut pool = BlobPool::<[u8]>::with_alignment(alignment);
+        let mut pool_offsets = Vec::with_capacity(self.strings.len());
+        for string in self.strings.values() {
+            let mut encoded = vec![];
+            match (self.encoding, self.null_terminated) {
+                (TextEncoding::Utf8, false) => encoded.extend_from_slice(string.as_ref().as_bytes()),
+                (TextEncoding::Utf8, true) => null_terminated_string(string.as_ref(), &mut encoded, options)?,
+                (TextEncoding::Utf16, false) => utf16_string(string.as_ref(), &mut encoded, options)?,
+                (TextEncoding::Utf16, true) => utf16_null_string(string.as_ref(), &mut encoded, options)?,
+            }
+            pool_offsets.push(pool.insert(&encoded[..], options)?);
+        }
+
+        let count = len_as::<P>(self.strings.len())?;
+        let mut header_size: usize = byte_len_of(&count, options)?;
+        let offset_size: usize = byte_len_of(&len_as::<P>(0)?, options)?;
+        for locale in self.strings.keys() {
+            header_size += byte_len_of::<usize, _>(locale, options)? + offset_size;
+        }
+        let pool_start = header_size + padding_for(header_size as u64, alignment)?;
+
+        BinWrite::write_options(&count, writer, options)?;
+        for (locale, offset) in self.strings.keys().zip(pool_offsets) {
+            BinWrite::write_options(locale, writer, options)?;
+            BinWrite::write_options(&len_as::<P>(pool_start + offset)?, writer, options)?;
+        }
+        pad(writer, pool_start - header_size)?;
+        BinWrite::write_options(&pool, writer, options)
+    }
+}
+
+/// A preprocessor for writing a locale to string map as a localization block. See
+/// [LocalizedStrings].
+pub fn localized_strings<K, S, P>(encoding: TextEncoding, null_terminated: bool) -> impl Fn(&BTreeMap<K, S>) -> LocalizedStrings<'_, K, S, P> {
+    move |strings| LocalizedStrings::new(strings, encoding, null_terminated)
+}
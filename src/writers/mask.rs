@@ -0,0 +1,55 @@
+use super::*;
+
+/// A [StreamProcessor] which XORs everything written with a repeating 4 byte key, as used for
+/// WebSocket client frames
+#[derive(Clone, Copy, Debug)]
+pub struct XorMask {
+    key: [u8; 4],
+    pos: usize,
+}
+
+impl XorMask {
+    pub fn new(key: [u8; 4]) -> Self {
+        XorMask { key, pos: 0 }
+    }
+}
+
+impl StreamProcessor for XorMask {
+    fn process<W: Write>(&mut self, data: &[u8], writer: &mut W) -> Result<()> {
+        let masked: Vec<u8> = data.iter()
+            .zip(self.key.iter().cycle().skip(self.pos % 4))
+            .map(|(byte, key)| byte ^ key)
+            .collect();
+        self.pos += data.len();
+        writer.write_all(&masked)
+    }
+
+    fn finish<W: Write>(self, _writer: &mut W, _options: &WriterOption) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// A built in writer which XORs the value with the mask key from the
+/// [WriterOption::mask](crate::WriterOption::mask), or writes it as-is if there is no key. Use
+/// `#[binwrite(with(writers::masked))]`.
+pub fn masked<T: BinWrite + ?Sized, W: Write>(value: &T, writer: &mut W, options: &WriterOption) -> Result<()> {
+    match options.mask {
+        Some(key) => {
+            let mut writer = ProcessingWriter::new(writer, XorMask::new(key));
+            BinWrite::write_options(value, &mut writer, options)?;
+            writer.finish(options)
+        }
+        None => BinWrite::write_options(value, writer, options)
+    }
+}
+
+/// A built in writer which writes the mask key from the
+/// [WriterOption::mask](crate::WriterOption::mask) (if any) in place of the field, so a frame can
+/// include the key its payload is masked with. Use `#[binwrite(with(writers::mask_key))]` on a
+/// placeholder field such as `()`.
+pub fn mask_key<T: ?Sized, W: Write>(_value: &T, writer: &mut W, options: &WriterOption) -> Result<()> {
+    match options.mask {
+        Some(key) => writer.write_all(&key),
+        None => Ok(())
+    }
+}
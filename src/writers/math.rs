@@ -0,0 +1,78 @@
+use super::*;
+
+/// A built in writer for writing a matrix stored as an array of rows in row-major order (each
+/// row in turn). Use `#[binwrite(with(writers::row_major))]`.
+pub fn row_major<T, W, const R: usize, const C: usize>(matrix: &[[T; C]; R], writer: &mut W, options: &WriterOption) -> Result<()>
+    where T: BinWrite,
+          W: Write,
+{
+    for component in matrix.iter().flatten() {
+        BinWrite::write_options(component, writer, options)?;
+    }
+    Ok(())
+}
+
+/// A built in writer for writing a matrix stored as an array of rows in column-major order (each
+/// column in turn), as expected by OpenGL-style engines. Use
+/// `#[binwrite(with(writers::column_major))]`.
+pub fn column_major<T, W, const R: usize, const C: usize>(matrix: &[[T; C]; R], writer: &mut W, options: &WriterOption) -> Result<()>
+    where T: BinWrite,
+          W: Write,
+{
+    for column in 0..C {
+        for row in matrix {
+            BinWrite::write_options(&row[column], writer, options)?;
+        }
+    }
+    Ok(())
+}
+
+/// A quaternion with `x`, `y`, `z` (vector) and `w` (scalar) components. Implemented for `[T; 4]`
+/// stored as `[x, y, z, w]`, and for `mint::Quaternion` with the `mint` feature.
+pub trait QuaternionParts {
+    type Component: BinWrite;
+
+    fn xyzw(&self) -> [&Self::Component; 4];
+}
+
+impl<T: BinWrite> QuaternionParts for [T; 4] {
+    type Component = T;
+
+    fn xyzw(&self) -> [&T; 4] {
+        [&self[0], &self[1], &self[2], &self[3]]
+    }
+}
+
+#[cfg(feature = "mint")]
+impl<T: BinWrite> QuaternionParts for mint::Quaternion<T> {
+    type Component = T;
+
+    fn xyzw(&self) -> [&T; 4] {
+        [&self.v.x, &self.v.y, &self.v.z, &self.s]
+    }
+}
+
+/// A built in writer for writing a quaternion as x, y, z then w. Use
+/// `#[binwrite(with(writers::quaternion_xyzw))]`.
+pub fn quaternion_xyzw<Q, W>(quaternion: &Q, writer: &mut W, options: &WriterOption) -> Result<()>
+    where Q: QuaternionParts,
+          W: Write,
+{
+    for component in quaternion.xyzw().iter() {
+        BinWrite::write_options(*component, writer, options)?;
+    }
+    Ok(())
+}
+
+/// A built in writer for writing a quaternion as w, x, y then z. Use
+/// `#[binwrite(with(writers::quaternion_wxyz))]`.
+pub fn quaternion_wxyz<Q, W>(quaternion: &Q, writer: &mut W, options: &WriterOption) -> Result<()>
+    where Q: QuaternionParts,
+          W: Write,
+{
+    let [x, y, z, w] = quaternion.xyzw();
+    for component in [w, x, y, z].iter() {
+        BinWrite::write_options(*component, writer, options)?;
+    }
+    Ok(())
+}
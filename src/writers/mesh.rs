@@ -0,0 +1,202 @@
+//! # Example:
+//! ```rust
+//! use binwrite::BinWrite;
+//! use binwrite::writers::mesh::{Stl, StlTriangle};
+//!
+//! let triangle = StlTriangle::new([[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]]);
+//! assert_eq!(triangle.normal, [0.0, 0.0, 1.0]);
+//!
+//! let mut bytes = vec![];
+//! Stl::new(b"binwrite", &[triangle]).write(&mut bytes).unwrap();
+//!
+//! assert_eq!(bytes.len(), 80 + 4 + 50);
+//! assert_eq!(&bytes[80..84], &[1, 0, 0, 0]);
+//! ```
+use super::*;
+use std::io::{Error, ErrorKind};
+
+/// A single triangle of an STL file
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StlTriangle {
+    pub normal: [f32; 3],
+    /// The corners in counter-clockwise order when viewed from outside
+    pub vertices: [[f32; 3]; 3],
+    /// The attribute byte count, which most software leaves as 0
+    pub attributes: u16,
+}
+
+impl StlTriangle {
+    /// A triangle with its normal computed from the winding of its vertices
+    pub fn new(vertices: [[f32; 3]; 3]) -> Self {
+        let [a, b, c] = vertices;
+        let u = [b[0] - a[0], b[1] - a[1], b[2] - a[2]];
+        let v = [c[0] - a[0], c[1] - a[1], c[2] - a[2]];
+        let cross = [u[1] * v[2] - u[2] * v[1], u[2] * v[0] - u[0] * v[2], u[0] * v[1] - u[1] * v[0]];
+        let len = (cross[0] * cross[0] + cross[1] * cross[1] + cross[2] * cross[2]).sqrt();
+        let normal = if len > 0.0 { [cross[0] / len, cross[1] / len, cross[2] / len] } else { [0.0; 3] };
+        StlTriangle { normal, vertices, attributes: 0 }
+    }
+}
+
+/// A binary STL file: an 80 byte header, the triangle count and then the triangles. STL is always
+/// little endian, so the endianness in the options is ignored. The header is padded with zeroes,
+/// and must not begin with `solid`, which marks an ASCII STL file.
+pub struct Stl<'a> {
+    pub header: &'a [u8],
+    pub triangles: &'a [StlTriangle],
+}
+
+impl<'a> Stl<'a> {
+    pub fn new(header: &'a [u8], triangles: &'a [StlTriangle]) -> Self {
+        Stl { header, triangles }
+    }
+}
+
+impl<'a> BinWrite for Stl<'a> {
+    fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+        if self.header.len() > 80 || self.header.starts_with(b"solid") {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "STL headers must be at most 80 bytes and not start with \"solid\""
+            ));
+        }
+        let options = &WriterOption { endian: Endian::Little, ..options.clone() };
+        writer.write_all(self.header)?;
+        pad(writer, 80 - self.header.len())?;
+        BinWrite::write_options(&len_as::<u32>(self.triangles.len())?, writer, options)?;
+
+        // Triangles are packed into one buffer so the file is written in a single call
+        let mut bytes = Vec::with_capacity(self.triangles.len() * 50);
+        for triangle in self.triangles {
+            (triangle.normal, triangle.vertices, triangle.attributes).write_options(&mut bytes, options)?;
+        }
+        writer.write_all(&bytes)
+    }
+}
+
+/// The type of a PLY property
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PlyType {
+    Char,
+    UChar,
+    Short,
+    UShort,
+    Int,
+    UInt,
+    Float,
+    Double,
+}
+
+impl PlyType {
+    fn name(&self) -> &'static str {
+        match self {
+            PlyType::Char => "char",
+            PlyType::UChar => "uchar",
+            PlyType::Short => "short",
+            PlyType::UShort => "ushort",
+            PlyType::Int => "int",
+            PlyType::UInt => "uint",
+            PlyType::Float => "float",
+            PlyType::Double => "double",
+        }
+    }
+}
+
+/// A property of each item of a PLY element
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PlyProperty {
+    Scalar { name: String, ty: PlyType },
+    /// A variable-length list, stored as a count followed by that many items
+    List { name: String, count: PlyType, item: PlyType },
+}
+
+/// The declaration of an element (such as `vertex` or `face`) in a PLY header
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PlyElement {
+    pub name: String,
+    pub count: usize,
+    pub properties: Vec<PlyProperty>,
+}
+
+/// The text header of a binary PLY file. The format line is `binary_big_endian` or
+/// `binary_little_endian` to match the endianness in the options, so the elements should be
+/// written after it with the same options, in order, with fields matching the declared properties.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PlyHeader {
+    pub comments: Vec<String>,
+    pub elements: Vec<PlyElement>,
+}
+
+impl BinWrite for PlyHeader {
+    fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+        let format = match options.endian.resolve() {
+            Endian::Big => "binary_big_endian",
+            _ => "binary_little_endian",
+        };
+        let mut header = format!("ply\nformat {} 1.0\n", format);
+        for comment in &self.comments {
+            header += &format!("comment {}\n", comment);
+        }
+        for element in &self.elements {
+            header += &format!("element {} {}\n", element.name, element.count);
+            for property in &element.properties {
+                header += &match property {
+                    PlyProperty::Scalar { name, ty } => format!("property {} {}\n", ty.name(), name),
+                    PlyProperty::List { name, count, item } => {
+                        format!("property list {} {} {}\n", count.name(), item.name(), name)
+                    }
+                };
+            }
+        }
+        header += "end_header\n";
+        writer.write_all(header.as_bytes())
+    }
+}
+
+/// A binary PLY mesh with `float` x/y/z vertices and faces as `uchar` counted lists of `uint`
+/// vertex indices, the layout most tools expect
+pub struct PlyMesh<'a> {
+    pub vertices: &'a [[f32; 3]],
+    pub faces: &'a [Vec<u32>],
+}
+
+impl<'a> PlyMesh<'a> {
+    pub fn new(vertices: &'a [[f32; 3]], faces: &'a [Vec<u32>]) -> Self {
+        PlyMesh { vertices, faces }
+    }
+
+    pub fn header(&self) -> PlyHeader {
+        let scalar = |name: &str| PlyProperty::Scalar { name: name.into(), ty: PlyType::Float };
+        PlyHeader {
+            comments: vec![],
+            elements: vec![
+                PlyElement {
+                    name: String::from("vertex"),
+                    count: self.vertices.len(),
+                    properties: vec![scalar("x"), scalar("y"), scalar("z")],
+                },
+                PlyElement {
+                    name: String::from("face"),
+                    count: self.faces.len(),
+                    properties: vec![PlyProperty::List {
+                        name: String::from("vertex_indices"),
+                        count: PlyType::UChar,
+                        item: PlyType::UInt,
+                    }],
+                },
+            ],
+        }
+    }
+}
+
+impl<'a> BinWrite for PlyMesh<'a> {
+    fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+        self.header().write_options(writer, options)?;
+        BinWrite::write_options(self.vertices, writer, options)?;
+        for face in self.faces {
+            BinWrite::write_options(&len_as::<u8>(face.len())?, writer, options)?;
+            BinWrite::write_options(face, writer, options)?;
+        }
+        Ok(())
+    }
+}
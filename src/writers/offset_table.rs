@@ -0,0 +1,49 @@
+use super::*;
+use std::convert::TryFrom;
+use std::marker::PhantomData;
+
+/// A layout which writes a table of `P` offsets, one per item, followed by the items themselves.
+/// Offsets are relative to the start of the layout, and are computed with a size pass over each
+/// item (see [byte_len_of]). Use `#[binwrite(with(OffsetTable::<u32>::write))]` on a `Vec<T>`
+/// for the offsets and data, or `#[binwrite(with(OffsetTable::<u32>::write_counted))]` to also
+/// write the item count as a `P` before the table, covering the common
+/// "count, offsets[], data" layout.
+pub struct OffsetTable<P>(PhantomData<P>);
+
+impl<P: TryFrom<usize> + BinWrite> OffsetTable<P> {
+    /// Write the offset table followed by the items
+    pub fn write<C, T, W>(items: &C, writer: &mut W, options: &WriterOption) -> Result<()>
+        where C: AsRef<[T]> + ?Sized,
+              T: BinWrite,
+              W: Write,
+    {
+        Self::write_table(items.as_ref(), 0, writer, options)
+    }
+
+    /// Write the number of items, then the offset table, then the items. Offsets include the
+    /// size of the count.
+    pub fn write_counted<C, T, W>(items: &C, writer: &mut W, options: &WriterOption) -> Result<()>
+        where C: AsRef<[T]> + ?Sized,
+              T: BinWrite,
+              W: Write,
+    {
+        let items = items.as_ref();
+        let count: P = len_as(items.len())?;
+        let count_size: usize = byte_len_of(&count, options)?;
+        BinWrite::write_options(&count, writer, options)?;
+        Self::write_table(items, count_size, writer, options)
+    }
+
+    fn write_table<T: BinWrite, W: Write>(items: &[T], start: usize, writer: &mut W, options: &WriterOption) -> Result<()> {
+        let entry_size: usize = byte_len_of(&len_as::<P>(0)?, options)?;
+        let mut offset = start + entry_size * items.len();
+        for item in items {
+            BinWrite::write_options(&len_as::<P>(offset)?, writer, options)?;
+            offset += byte_len_of::<usize, _>(item, options)?;
+        }
+        for item in items {
+            BinWrite::write_options(item, writer, options)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,31 @@
+use super::*;
+
+fn option_with_flag<T, P, W>(value: &Option<T>, present: P, absent: P, writer: &mut W, options: &WriterOption) -> Result<()>
+    where T: BinWrite,
+          P: BinWrite,
+          W: Write,
+{
+    match value {
+        Some(value) => {
+            BinWrite::write_options(&present, writer, options)?;
+            BinWrite::write_options(value, writer, options)
+        }
+        None => BinWrite::write_options(&absent, writer, options)
+    }
+}
+
+/// A built in writer for Options which writes a u8 of 1 followed by the value if present, or a
+/// single u8 of 0 if not. Use `#[binwrite(with(writers::option_byte))]`.
+pub fn option_byte<T: BinWrite, W: Write>(value: &Option<T>, writer: &mut W, options: &WriterOption) -> Result<()> {
+    option_with_flag(value, 1u8, 0u8, writer, options)
+}
+
+/// Same as [option_byte] but the presence flag is a u16
+pub fn option_u16<T: BinWrite, W: Write>(value: &Option<T>, writer: &mut W, options: &WriterOption) -> Result<()> {
+    option_with_flag(value, 1u16, 0u16, writer, options)
+}
+
+/// Same as [option_byte] but the presence flag is a u32
+pub fn option_u32<T: BinWrite, W: Write>(value: &Option<T>, writer: &mut W, options: &WriterOption) -> Result<()> {
+    option_with_flag(value, 1u32, 0u32, writer, options)
+}
@@ -0,0 +1,70 @@
+use super::*;
+use std::io::{Error, ErrorKind};
+
+/// A stateful encoder (such as a Huffman or arithmetic coder from another crate) which fields can
+/// be written through without first collecting them into a `Vec<u8>`. Set one in
+/// [WriterOption::payload_encoder] and wrap fields in [PayloadEncoded]. The same encoder is used
+/// for every field, so it can keep adaptive state (such as symbol models) between them.
+pub trait PayloadEncoder: Send {
+    /// Encode the next chunk of a field's bytes, writing any encoded output to `out`
+    fn encode(&mut self, data: &[u8], out: &mut dyn Write) -> Result<()>;
+
+    /// Called at the end of each field, for flushing any buffered bits or symbols to `out`
+    fn finish(&mut self, out: &mut dyn Write) -> Result<()>;
+}
+
+/// Forwards every write to the encoder
+struct EncoderWriter<'a, W: Write> {
+    encoder: &'a mut dyn PayloadEncoder,
+    out: &'a mut W,
+}
+
+impl<'a, W: Write> Write for EncoderWriter<'a, W> {
+    fn write(&mut self, data: &[u8]) -> Result<usize> {
+        self.encoder.encode(data, self.out)?;
+        Ok(data.len())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.out.flush()
+    }
+}
+
+/// A wrapper which writes the inner value through the [PayloadEncoder] in the options, chunk by
+/// chunk as it is written. Returns an error if no encoder is set. The encoder is locked while the
+/// value is written, so the value is written without one and nested PayloadEncoded values are an
+/// error rather than a deadlock. Use `#[binwrite(preprocessor(payload_encoded))]`.
+pub struct PayloadEncoded<'a, T: ?Sized> {
+    value: &'a T,
+}
+
+impl<'a, T: BinWrite + ?Sized> PayloadEncoded<'a, T> {
+    pub fn new(value: &'a T) -> Self {
+        PayloadEncoded { value }
+    }
+}
+
+impl<'a, T: BinWrite + ?Sized> BinWrite for PayloadEncoded<'a, T> {
+    fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+        let encoder = options.payload_encoder.as_ref()
+            .ok_or_else(|| Error::new(
+                ErrorKind::InvalidInput,
+                "no payload encoder set in the writer options (payload encoded values can't be nested)"
+            ))?;
+        let mut encoder = encoder.lock()
+            .map_err(|_| Error::other("payload encoder lock was poisoned"))?;
+
+        let mut inner_options = options.clone();
+        inner_options.payload_encoder = None;
+
+        let mut encoding = EncoderWriter { encoder: &mut *encoder, out: writer };
+        BinWrite::write_options(self.value, &mut encoding, &inner_options)?;
+        encoder.finish(writer)
+    }
+}
+
+/// A preprocessor for writing a value through the options' payload encoder. See
+/// [PayloadEncoded].
+pub fn payload_encoded<T: BinWrite + ?Sized>(value: &T) -> PayloadEncoded<'_, T> {
+    PayloadEncoded::new(value)
+}
@@ -0,0 +1,95 @@
+use super::*;
+
+/// The sample format [Pcm] converts to
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PcmFormat {
+    /// Unsigned 8-bit, centered on 128
+    U8,
+    I16,
+    /// Signed 24-bit, packed into 3 bytes
+    I24,
+    /// 32-bit float, clamped to -1.0..=1.0
+    F32,
+}
+
+/// Whether to dither samples when reducing them to integers
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Dither {
+    Off,
+    /// Triangular (TPDF) dither of up to one least significant bit, from a deterministic
+    /// generator seeded with the given (non-zero) value so output is reproducible
+    Triangular(u32),
+}
+
+/// A small xorshift generator for dither noise
+struct Noise(u32);
+
+impl Noise {
+    /// Uniform noise in 0.0..1.0
+    fn next(&mut self) -> f32 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 17;
+        self.0 ^= self.0 << 5;
+        (self.0 >> 8) as f32 / (1u32 << 24) as f32
+    }
+}
+
+/// A wrapper which converts `f32` samples (nominally -1.0..=1.0) to PCM when written, clamping
+/// out of range samples. Integer formats use the endianness for byte order. Samples are
+/// converted into a single buffer which is written at once. Use
+/// `#[binwrite(preprocessor(pcm(PcmFormat::I16, Dither::Off)))]` on a `Vec<f32>`.
+pub struct Pcm<'a, C: ?Sized> {
+    samples: &'a C,
+    format: PcmFormat,
+    dither: Dither,
+}
+
+impl<'a, C: AsRef<[f32]> + ?Sized> Pcm<'a, C> {
+    pub fn new(samples: &'a C, format: PcmFormat, dither: Dither) -> Self {
+        Pcm { samples, format, dither }
+    }
+}
+
+impl<'a, C: AsRef<[f32]> + ?Sized> BinWrite for Pcm<'a, C> {
+    fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+        let samples = self.samples.as_ref();
+        let big = options.endian.resolve() == Endian::Big;
+        let mut noise = match self.dither {
+            Dither::Triangular(seed) => Some(Noise(seed.max(1))),
+            Dither::Off => None,
+        };
+        let mut quantize = |sample: f32, max: f32| {
+            let dither = noise.as_mut().map_or(0.0, |noise| noise.next() - noise.next());
+            (sample.clamp(-1.0, 1.0) * max + dither).round().clamp(-max - 1.0, max) as i32
+        };
+
+        let mut bytes = Vec::with_capacity(samples.len() * 4);
+        for &sample in samples {
+            match self.format {
+                PcmFormat::U8 => bytes.push((quantize(sample, 127.0) + 128) as u8),
+                PcmFormat::I16 => {
+                    let sample = quantize(sample, 32767.0) as i16;
+                    bytes.extend_from_slice(&if big { sample.to_be_bytes() } else { sample.to_le_bytes() });
+                }
+                PcmFormat::I24 => {
+                    let sample = quantize(sample, 8_388_607.0);
+                    if big {
+                        bytes.extend_from_slice(&sample.to_be_bytes()[1..]);
+                    } else {
+                        bytes.extend_from_slice(&sample.to_le_bytes()[..3]);
+                    }
+                }
+                PcmFormat::F32 => {
+                    let sample = sample.clamp(-1.0, 1.0);
+                    bytes.extend_from_slice(&if big { sample.to_be_bytes() } else { sample.to_le_bytes() });
+                }
+            }
+        }
+        writer.write_all(&bytes)
+    }
+}
+
+/// A preprocessor for converting `f32` samples to PCM. See [Pcm].
+pub fn pcm<C: AsRef<[f32]> + ?Sized>(format: PcmFormat, dither: Dither) -> impl Fn(&C) -> Pcm<'_, C> {
+    move |samples| Pcm::new(samples, format, dither)
+}
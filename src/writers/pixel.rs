@@ -0,0 +1,105 @@
+use super::*;
+
+/// A color which can be converted to 8-bit RGBA. Implemented for `[u8; 4]` (`[r, g, b, a]`) and
+/// `u32` (`0xRRGGBBAA`).
+pub trait Rgba {
+    fn to_rgba(&self) -> [u8; 4];
+}
+
+impl Rgba for [u8; 4] {
+    fn to_rgba(&self) -> [u8; 4] {
+        *self
+    }
+}
+
+impl Rgba for u32 {
+    fn to_rgba(&self) -> [u8; 4] {
+        self.to_be_bytes()
+    }
+}
+
+/// A pixel format to convert colors to when writing. 16-bit formats are written as a u16 using
+/// the endianness, with the first channel in the name in the most significant bits. Byte formats
+/// are written one channel per byte in the order of the name.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PixelFormat {
+    Rgb565,
+    Rgba4444,
+    Argb4444,
+    Rgba5551,
+    Argb1555,
+    Rgb888,
+    Bgr888,
+    Rgba8888,
+    Bgra8888,
+    Argb8888,
+    Abgr8888,
+}
+
+/// Scale an 8-bit channel down to `bits` bits, rounding to nearest
+fn scale(channel: u8, bits: u32) -> u16 {
+    let max = (1u32 << bits) - 1;
+    ((channel as u32 * max + 127) / 255) as u16
+}
+
+impl PixelFormat {
+    /// Write a single color in this format
+    pub fn write_color<W: Write>(&self, [r, g, b, a]: [u8; 4], writer: &mut W, options: &WriterOption) -> Result<()> {
+        let packed = match self {
+            PixelFormat::Rgb565 => (scale(r, 5) << 11) | (scale(g, 6) << 5) | scale(b, 5),
+            PixelFormat::Rgba4444 => (scale(r, 4) << 12) | (scale(g, 4) << 8) | (scale(b, 4) << 4) | scale(a, 4),
+            PixelFormat::Argb4444 => (scale(a, 4) << 12) | (scale(r, 4) << 8) | (scale(g, 4) << 4) | scale(b, 4),
+            PixelFormat::Rgba5551 => (scale(r, 5) << 11) | (scale(g, 5) << 6) | (scale(b, 5) << 1) | scale(a, 1),
+            PixelFormat::Argb1555 => (scale(a, 1) << 15) | (scale(r, 5) << 10) | (scale(g, 5) << 5) | scale(b, 5),
+            PixelFormat::Rgb888 => return writer.write_all(&[r, g, b]),
+            PixelFormat::Bgr888 => return writer.write_all(&[b, g, r]),
+            PixelFormat::Rgba8888 => return writer.write_all(&[r, g, b, a]),
+            PixelFormat::Bgra8888 => return writer.write_all(&[b, g, r, a]),
+            PixelFormat::Argb8888 => return writer.write_all(&[a, r, g, b]),
+            PixelFormat::Abgr8888 => return writer.write_all(&[a, b, g, r]),
+        };
+        BinWrite::write_options(&packed, writer, options)
+    }
+}
+
+/// A wrapper which converts a color, or a slice or Vec of colors, to a [PixelFormat] when
+/// written. Use `#[binwrite(preprocessor(pixel_format(PixelFormat::Rgb565)))]` on a `[u8; 4]`,
+/// `u32` or `Vec` of either.
+pub struct Converted<'a, T: ?Sized> {
+    value: &'a T,
+    format: PixelFormat,
+}
+
+impl<'a, T: ?Sized> Converted<'a, T> {
+    pub fn new(value: &'a T, format: PixelFormat) -> Self {
+        Converted { value, format }
+    }
+}
+
+impl<'a, T: Rgba> BinWrite for Converted<'a, T> {
+    fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+        self.format.write_color(self.value.to_rgba(), writer, options)
+    }
+}
+
+impl<'a, T: Rgba> BinWrite for Converted<'a, [T]> {
+    fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+        for color in self.value {
+            self.format.write_color(color.to_rgba(), writer, options)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a, T: Rgba> BinWrite for Converted<'a, Vec<T>> {
+    fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+        Converted::new(&self.value[..], self.format).write_options(writer, options)
+    }
+}
+
+/// A preprocessor for converting colors to a pixel format. See [Converted].
+pub fn pixel_format<T: ?Sized>(format: PixelFormat) -> impl Fn(&T) -> Converted<'_, T>
+    where for<'a> Converted<'a, T>: BinWrite,
+{
+    move |value| Converted::new(value, format)
+}
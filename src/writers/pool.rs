@@ -0,0 +1,88 @@
+use super::*;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
+/// A pool of serialized values which stores each distinct value once, for formats where entries
+/// share data (such as meshes or textures) by offset. Values are serialized as they are inserted
+/// and identical serializations share an offset. The pool is written all at once by its BinWrite
+/// implementation, with endianness having no further effect.
+///
+/// ```rust
+/// use binwrite::{BinWrite, WriterOption};
+/// use binwrite::writers::BlobPool;
+///
+/// let options = WriterOption::default();
+/// let mut pool = BlobPool::<[u16]>::new();
+/// assert_eq!(pool.insert(&[1, 2], &options).unwrap(), 0);
+/// assert_eq!(pool.insert(&[3], &options).unwrap(), 4);
+/// assert_eq!(pool.insert(&[1, 2], &options).unwrap(), 0);
+///
+/// let mut bytes = vec![];
+/// pool.write(&mut bytes).unwrap();
+/// assert_eq!(bytes.len(), 6);
+/// ```
+pub struct BlobPool<T: ?Sized> {
+    data: Vec<u8>,
+    offsets: HashMap<Vec<u8>, usize>,
+    alignment: usize,
+    _marker: PhantomData<fn(&T)>,
+}
+
+impl<T: BinWrite + ?Sized> BlobPool<T> {
+    pub fn new() -> Self {
+        Self::with_alignment(1)
+    }
+
+    /// Create a pool where each distinct value starts at a multiple of `alignment` within the
+    /// pool
+    pub fn with_alignment(alignment: usize) -> Self {
+        BlobPool {
+            data: Vec::new(),
+            offsets: HashMap::new(),
+            alignment,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Add a value to the pool, returning its offset within the pool. If an identical value has
+    /// already been added, the existing offset is returned instead.
+    pub fn insert(&mut self, value: &T, options: &WriterOption) -> Result<usize> {
+        let mut bytes = vec![];
+        BinWrite::write_options(value, &mut bytes, options)?;
+        if let Some(&offset) = self.offsets.get(&bytes) {
+            return Ok(offset);
+        }
+
+        let padding = padding_for(self.data.len() as u64, self.alignment)?;
+        pad(&mut self.data, padding)?;
+        let offset = self.data.len();
+        self.data.extend_from_slice(&bytes);
+        self.offsets.insert(bytes, offset);
+        Ok(offset)
+    }
+
+    /// The size of the pool in bytes
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+impl<T: BinWrite + ?Sized> Default for BlobPool<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: ?Sized> BinWrite for BlobPool<T> {
+    fn write_options<W: Write>(&self, writer: &mut W, _options: &WriterOption) -> Result<()> {
+        writer.write_all(&self.data)
+    }
+}
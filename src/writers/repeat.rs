@@ -0,0 +1,28 @@
+use super::*;
+
+/// A wrapper which writes the inner value `count` times, for things like mirrored table entries
+/// or repeated sync words. Use `#[binwrite(preprocessor(repeat(4)))]` to write a field 4 times.
+pub struct Repeat<'a, T: ?Sized> {
+    value: &'a T,
+    count: usize,
+}
+
+impl<'a, T: ?Sized> Repeat<'a, T> {
+    pub fn new(value: &'a T, count: usize) -> Self {
+        Repeat { value, count }
+    }
+}
+
+impl<'a, T: BinWrite + ?Sized> BinWrite for Repeat<'a, T> {
+    fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+        for _ in 0..self.count {
+            BinWrite::write_options(self.value, writer, options)?;
+        }
+        Ok(())
+    }
+}
+
+/// A preprocessor for writing a value `count` times. See [Repeat].
+pub fn repeat<T: BinWrite + ?Sized>(count: usize) -> impl Fn(&T) -> Repeat<'_, T> {
+    move |value| Repeat::new(value, count)
+}
@@ -0,0 +1,28 @@
+use super::*;
+
+/// A built in writer for writing the items of an array, slice or Vec in reverse order, for
+/// formats which store tables back-to-front. Use `#[binwrite(with(writers::reversed))]`.
+pub fn reversed<C, T, W>(items: &C, writer: &mut W, options: &WriterOption) -> Result<()>
+    where C: AsRef<[T]> + ?Sized,
+          T: BinWrite,
+          W: Write,
+{
+    for item in items.as_ref().iter().rev() {
+        BinWrite::write_options(item, writer, options)?;
+    }
+    Ok(())
+}
+
+/// A postprocessor for reversing the order of bytes within each `word_size` byte word, for
+/// word-swapped blobs. Any trailing bytes which don't make up a full word are left as-is. Use
+/// `#[binwrite(postprocessor(swap_bytes(2)))]` to swap every pair of bytes.
+pub fn swap_bytes(word_size: usize) -> impl Fn(Vec<u8>) -> Vec<u8> {
+    move |mut bytes| {
+        if word_size > 1 {
+            for word in bytes.chunks_exact_mut(word_size) {
+                word.reverse();
+            }
+        }
+        bytes
+    }
+}
@@ -0,0 +1,117 @@
+//! # Example:
+//! ```rust
+//! use binwrite::BinWrite;
+//! use binwrite::writers::riff::Wav;
+//!
+//! let samples = [0i16, 1000, -1000, 0];
+//!
+//! let mut bytes = vec![];
+//! Wav::new(2, 44100, &samples).write(&mut bytes).unwrap();
+//!
+//! assert_eq!(&bytes[..4], b"RIFF");
+//! assert_eq!(&bytes[8..16], b"WAVEfmt ");
+//! assert_eq!(bytes.len(), 12 + (8 + 16) + (8 + 8));
+//! ```
+use super::*;
+
+/// A RIFF file: a `RIFF` chunk containing a form type (such as `WAVE`) followed by the inner
+/// value, which is usually a series of [Chunk]s
+pub struct RiffChunk<'a, T: ?Sized> {
+    pub form: FourCC,
+    pub chunks: &'a T,
+}
+
+impl<'a, T: BinWrite + ?Sized> BinWrite for RiffChunk<'a, T> {
+    fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+        Chunk::new(b"RIFF", &(self.form, self.chunks)).write_options(writer, options)
+    }
+}
+
+/// A sample type that can be stored in a WAV file
+pub trait Sample: BinWrite {
+    /// The WAVE format tag, 1 for integer PCM and 3 for IEEE float
+    const FORMAT: u16;
+    const BITS: u16;
+}
+
+macro_rules! sample_impl {
+    ($($type_name:ty => $format:literal),*$(,)?) => {
+        $(
+            impl Sample for $type_name {
+                const FORMAT: u16 = $format;
+                const BITS: u16 = (std::mem::size_of::<$type_name>() * 8) as u16;
+            }
+        )*
+    }
+}
+
+sample_impl!(u8 => 1, i16 => 1, i32 => 1, f32 => 3, f64 => 3);
+
+/// The `fmt ` chunk of a WAV file, describing the sample format
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FmtChunk {
+    pub format: u16,
+    pub channels: u16,
+    pub sample_rate: u32,
+    pub bits_per_sample: u16,
+}
+
+impl FmtChunk {
+    /// The format chunk for samples of type `S`
+    pub fn new<S: Sample>(channels: u16, sample_rate: u32) -> Self {
+        FmtChunk { format: S::FORMAT, channels, sample_rate, bits_per_sample: S::BITS }
+    }
+
+    /// The size of one sample for every channel, in bytes
+    pub fn block_align(&self) -> u16 {
+        self.channels * self.bits_per_sample.div_ceil(8)
+    }
+
+    pub fn byte_rate(&self) -> u32 {
+        self.sample_rate * self.block_align() as u32
+    }
+}
+
+impl BinWrite for FmtChunk {
+    fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+        let fields = (
+            self.format, self.channels, self.sample_rate, self.byte_rate(), self.block_align(),
+            self.bits_per_sample,
+        );
+        Chunk::new(b"fmt ", &fields).write_options(writer, options)
+    }
+}
+
+/// The `data` chunk of a WAV file, holding interleaved samples
+pub struct DataChunk<'a, S>(pub &'a [S]);
+
+impl<'a, S: Sample> BinWrite for DataChunk<'a, S> {
+    fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+        Chunk::new(b"data", self.0).write_options(writer, options)
+    }
+}
+
+/// A complete WAV file of interleaved samples, with the format chunk derived from the sample
+/// type. WAV files are always little endian, regardless of the options.
+pub struct Wav<'a, S> {
+    pub channels: u16,
+    pub sample_rate: u32,
+    pub samples: &'a [S],
+}
+
+impl<'a, S: Sample> Wav<'a, S> {
+    pub fn new(channels: u16, sample_rate: u32, samples: &'a [S]) -> Self {
+        Wav { channels, sample_rate, samples }
+    }
+}
+
+impl<'a, S: Sample> BinWrite for Wav<'a, S> {
+    fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+        let options = WriterOption {
+            endian: Endian::Little,
+            ..options.clone()
+        };
+        let chunks = (FmtChunk::new::<S>(self.channels, self.sample_rate), DataChunk(self.samples));
+        RiffChunk { form: FourCC(*b"WAVE"), chunks: &chunks }.write_options(writer, &options)
+    }
+}
@@ -0,0 +1,145 @@
+use super::*;
+use std::io::{Error, ErrorKind};
+
+/// A run-length encoding packet format. Data is split into units of `unit_size` bytes (1 for byte
+/// oriented formats, the pixel size for image formats), runs of at least `min_run` identical units
+/// are written as a run packet (header + a single unit) and everything else is written as literal
+/// packets (header + the units). `min_run`, `max_run` and `max_literal` must be at least 1.
+#[derive(Clone, Copy)]
+pub struct Rle {
+    pub unit_size: usize,
+    pub min_run: usize,
+    pub max_run: usize,
+    pub max_literal: usize,
+    /// Header byte for a literal packet of the given number of units, or `None` if the header
+    /// can't represent that many
+    pub literal_header: fn(usize) -> Option<u8>,
+    /// Header byte for a run packet of the given number of units, or `None` if the header can't
+    /// represent that many
+    pub run_header: fn(usize) -> Option<u8>,
+}
+
+impl Rle {
+    /// Apple PackBits: `n - 1` for literal packets, `1 - n` (as an i8) for runs, up to 128 bytes
+    pub const PACKBITS: Rle = Rle {
+        unit_size: 1,
+        min_run: 2,
+        max_run: 128,
+        max_literal: 128,
+        literal_header: packbits_literal_header,
+        run_header: packbits_run_header,
+    };
+
+    /// TGA style RLE: `n - 1` for literal packets, `0x80 | (n - 1)` for runs, up to 128 units
+    pub const fn tga(pixel_size: usize) -> Rle {
+        Rle {
+            unit_size: pixel_size,
+            min_run: 2,
+            max_run: 128,
+            max_literal: 128,
+            literal_header: tga_literal_header,
+            run_header: tga_run_header,
+        }
+    }
+
+    /// Encode `data` using this format. Returns an error if the data isn't a whole number of
+    /// units, or if the limits are zero or too large for the headers.
+    pub fn encode(&self, data: &[u8]) -> Result<Vec<u8>> {
+        if self.min_run == 0 || self.max_run == 0 || self.max_literal == 0 {
+            return Err(Error::new(ErrorKind::InvalidInput, "RLE run and literal limits must be at least 1"));
+        }
+        let unit_size = self.unit_size.max(1);
+        let chunks = data.chunks_exact(unit_size);
+        if !chunks.remainder().is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("data length {:#x} is not a multiple of the RLE unit size {}", data.len(), unit_size)
+            ));
+        }
+
+        let units: Vec<&[u8]> = chunks.collect();
+        let mut out = Vec::with_capacity(data.len());
+        let mut literal_start = 0;
+        let mut i = 0;
+        while i < units.len() {
+            let run = units[i..].iter()
+                .take(self.max_run)
+                .take_while(|unit| **unit == units[i])
+                .count();
+            if run >= self.min_run {
+                self.write_literals(&units[literal_start..i], &mut out)?;
+                out.push(header((self.run_header)(run), "run", run)?);
+                out.extend_from_slice(units[i]);
+                i += run;
+                literal_start = i;
+            } else {
+                i += 1;
+            }
+        }
+        self.write_literals(&units[literal_start..], &mut out)?;
+
+        Ok(out)
+    }
+
+    fn write_literals(&self, units: &[&[u8]], out: &mut Vec<u8>) -> Result<()> {
+        for packet in units.chunks(self.max_literal) {
+            out.push(header((self.literal_header)(packet.len()), "literal", packet.len())?);
+            for unit in packet {
+                out.extend_from_slice(unit);
+            }
+        }
+        Ok(())
+    }
+}
+
+fn header(header: Option<u8>, kind: &str, len: usize) -> Result<u8> {
+    header.ok_or_else(|| Error::new(
+        ErrorKind::InvalidInput,
+        format!("RLE {} packet of {} units does not fit in its header", kind, len)
+    ))
+}
+
+fn packbits_literal_header(len: usize) -> Option<u8> {
+    (1..=128).contains(&len).then(|| (len - 1) as u8)
+}
+
+fn packbits_run_header(len: usize) -> Option<u8> {
+    (1..=128).contains(&len).then(|| (1 - len as isize) as u8)
+}
+
+fn tga_literal_header(len: usize) -> Option<u8> {
+    (1..=128).contains(&len).then(|| (len - 1) as u8)
+}
+
+fn tga_run_header(len: usize) -> Option<u8> {
+    (1..=128).contains(&len).then(|| 0x80 | (len - 1) as u8)
+}
+
+/// Bytes which are run-length encoded when written. See [Rle].
+pub struct RleEncoded {
+    bytes: Vec<u8>,
+    format: Rle,
+}
+
+impl RleEncoded {
+    pub fn new(bytes: Vec<u8>, format: Rle) -> Self {
+        RleEncoded { bytes, format }
+    }
+}
+
+impl BinWrite for RleEncoded {
+    fn write_options<W: Write>(&self, writer: &mut W, _options: &WriterOption) -> Result<()> {
+        writer.write_all(&self.format.encode(&self.bytes)?)
+    }
+}
+
+/// A postprocessor for run-length encoding a field. Use
+/// `#[binwrite(postprocessor(rle(Rle::tga(4))))]` for a custom format.
+pub fn rle(format: Rle) -> impl Fn(Vec<u8>) -> RleEncoded {
+    move |bytes| RleEncoded::new(bytes, format)
+}
+
+/// A postprocessor for PackBits encoding a field. Use `#[binwrite(postprocessor(packbits))]`.
+pub fn packbits(bytes: Vec<u8>) -> RleEncoded {
+    RleEncoded::new(bytes, Rle::PACKBITS)
+}
@@ -0,0 +1,117 @@
+//! # Example:
+//! ```rust
+//! use binwrite::writers::rom::{fix_snes_checksum, LOROM_HEADER};
+//!
+//! let mut rom = vec![0u8; 0x8000];
+//! rom[0] = 1;
+//! fix_snes_checksum(&mut rom, LOROM_HEADER).unwrap();
+//!
+//! // 0x1FE from the complement and checksum fields, plus the single 1
+//! assert_eq!(&rom[LOROM_HEADER + 0x1C..LOROM_HEADER + 0x20], &[0x00, 0xFE, 0xFF, 0x01]);
+//! ```
+use std::io::{Error, ErrorKind, Result};
+
+/// Offset of the internal header in a LoROM SNES ROM (without a copier header)
+pub const LOROM_HEADER: usize = 0x7FC0;
+/// Offset of the internal header in a HiROM SNES ROM (without a copier header)
+pub const HIROM_HEADER: usize = 0xFFC0;
+
+/// The sum of every byte of an SNES ROM, with a ROM that isn't a power of two in size treated as
+/// its largest power of two followed by the rest mirrored to fill the same size again
+pub fn snes_checksum(rom: &[u8]) -> u16 {
+    fn sum(data: &[u8]) -> u32 {
+        data.iter().fold(0u32, |sum, byte| sum.wrapping_add(*byte as u32))
+    }
+
+    if rom.is_empty() {
+        return 0;
+    }
+    let base = 1usize << (usize::BITS - 1 - rom.len().leading_zeros());
+    let (first, rest) = rom.split_at(base);
+    if rest.is_empty() {
+        return sum(first) as u16;
+    }
+    let mirrored = base / rest.len();
+    sum(first).wrapping_add(sum(rest).wrapping_mul(mirrored as u32)) as u16
+}
+
+/// Fill in the checksum and checksum complement of an SNES ROM's internal header, which starts
+/// at `header` (usually [LOROM_HEADER] or [HIROM_HEADER]). Both are little endian.
+pub fn fix_snes_checksum(rom: &mut [u8], header: usize) -> Result<()> {
+    let fields = header + 0x1C..header + 0x20;
+    if fields.end > rom.len() {
+        return Err(Error::new(ErrorKind::InvalidInput, format!("SNES header at {:#x} is past the end of the ROM", header)));
+    }
+    // The checksum is computed with the complement and checksum set to 0xFFFF and 0
+    rom[fields.clone()].copy_from_slice(&[0xFF, 0xFF, 0, 0]);
+    let checksum = snes_checksum(rom);
+    rom[fields.start..fields.start + 2].copy_from_slice(&(!checksum).to_le_bytes());
+    rom[fields.start + 2..fields.end].copy_from_slice(&checksum.to_le_bytes());
+    Ok(())
+}
+
+/// The boot chip (CIC) of an N64 cartridge, which decides how its CRC is computed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Cic {
+    Cic6101,
+    Cic6102,
+    Cic6103,
+    Cic6105,
+    Cic6106,
+}
+
+const N64_CRC_START: usize = 0x1000;
+const N64_CRC_END: usize = 0x10_1000;
+
+/// The CRC pair of an N64 ROM (in big endian byte order), covering the 1 MiB after the boot
+/// code. Returns an error if the ROM is too small.
+pub fn n64_crc(rom: &[u8], cic: Cic) -> Result<(u32, u32)> {
+    if rom.len() < N64_CRC_END {
+        return Err(Error::new(ErrorKind::InvalidInput, format!("N64 ROM must be at least {:#x} bytes", N64_CRC_END)));
+    }
+    let word = |offset: usize| u32::from_be_bytes([rom[offset], rom[offset + 1], rom[offset + 2], rom[offset + 3]]);
+
+    let seed = match cic {
+        Cic::Cic6101 | Cic::Cic6102 => 0xF8CA_4DDCu32,
+        Cic::Cic6103 => 0xA388_6759,
+        Cic::Cic6105 => 0xDF26_F436,
+        Cic::Cic6106 => 0x1FEA_617A,
+    };
+    let (mut t1, mut t2, mut t3, mut t4, mut t5, mut t6) = (seed, seed, seed, seed, seed, seed);
+
+    for offset in (N64_CRC_START..N64_CRC_END).step_by(4) {
+        let d = word(offset);
+        let (sum, overflowed) = t6.overflowing_add(d);
+        if overflowed {
+            t4 = t4.wrapping_add(1);
+        }
+        t6 = sum;
+        t3 ^= d;
+        let r = d.rotate_left(d & 0x1F);
+        t5 = t5.wrapping_add(r);
+        if t2 > d {
+            t2 ^= r;
+        } else {
+            t2 ^= t6 ^ d;
+        }
+        if cic == Cic::Cic6105 {
+            t1 = t1.wrapping_add(word(0x0750 + (offset & 0xFF)) ^ d);
+        } else {
+            t1 = t1.wrapping_add(t5 ^ d);
+        }
+    }
+
+    Ok(match cic {
+        Cic::Cic6103 => ((t6 ^ t4).wrapping_add(t3), (t5 ^ t2).wrapping_add(t1)),
+        Cic::Cic6106 => (t6.wrapping_mul(t4).wrapping_add(t3), t5.wrapping_mul(t2).wrapping_add(t1)),
+        _ => (t6 ^ t4 ^ t3, t5 ^ t2 ^ t1),
+    })
+}
+
+/// Fill in the CRC pair at offsets 0x10 and 0x14 of an N64 ROM's header
+pub fn fix_n64_crc(rom: &mut [u8], cic: Cic) -> Result<()> {
+    let (crc1, crc2) = n64_crc(rom, cic)?;
+    rom[0x10..0x14].copy_from_slice(&crc1.to_be_bytes());
+    rom[0x14..0x18].copy_from_slice(&crc2.to_be_bytes());
+    Ok(())
+}
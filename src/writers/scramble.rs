@@ -0,0 +1,29 @@
+/// A postprocessor for swapping the high and low nibble of every byte. Use
+/// `#[binwrite(postprocessor(swap_nibbles))]`. For swapping pairs of bytes, see [swap_bytes](super::swap_bytes).
+pub fn swap_nibbles(bytes: Vec<u8>) -> Vec<u8> {
+    bytes.into_iter().map(|byte| byte.rotate_left(4)).collect()
+}
+
+/// A postprocessor for rotating the bits of every byte left by `amount`. Use
+/// `#[binwrite(postprocessor(rotate_bits_left(3)))]`.
+pub fn rotate_bits_left(amount: u32) -> impl Fn(Vec<u8>) -> Vec<u8> {
+    move |bytes| bytes.into_iter().map(|byte| byte.rotate_left(amount)).collect()
+}
+
+/// A postprocessor for rotating the bits of every byte right by `amount`. Use
+/// `#[binwrite(postprocessor(rotate_bits_right(3)))]`.
+pub fn rotate_bits_right(amount: u32) -> impl Fn(Vec<u8>) -> Vec<u8> {
+    move |bytes| bytes.into_iter().map(|byte| byte.rotate_right(amount)).collect()
+}
+
+/// A postprocessor for adding `value` to every byte, wrapping on overflow. Use
+/// `#[binwrite(postprocessor(add_constant(0x80)))]`.
+pub fn add_constant(value: u8) -> impl Fn(Vec<u8>) -> Vec<u8> {
+    move |bytes| bytes.into_iter().map(|byte| byte.wrapping_add(value)).collect()
+}
+
+/// A postprocessor for XORing every byte with `value`. Use
+/// `#[binwrite(postprocessor(xor_constant(0xFF)))]`.
+pub fn xor_constant(value: u8) -> impl Fn(Vec<u8>) -> Vec<u8> {
+    move |bytes| bytes.into_iter().map(|byte| byte ^ value).collect()
+}
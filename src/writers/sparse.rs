@@ -0,0 +1,73 @@
+use super::*;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::BuildHasher;
+use std::io::{Error, ErrorKind};
+
+/// A map from table index to entry, used by [SparseTable]
+pub trait SparseEntries<T> {
+    fn entry(&self, index: usize) -> Option<&T>;
+
+    /// The largest index in the map, if any
+    fn max_index(&self) -> Option<usize>;
+}
+
+impl<T> SparseEntries<T> for BTreeMap<usize, T> {
+    fn entry(&self, index: usize) -> Option<&T> {
+        self.get(&index)
+    }
+
+    fn max_index(&self) -> Option<usize> {
+        self.keys().next_back().copied()
+    }
+}
+
+impl<T, S: BuildHasher> SparseEntries<T> for HashMap<usize, T, S> {
+    fn entry(&self, index: usize) -> Option<&T> {
+        self.get(&index)
+    }
+
+    fn max_index(&self) -> Option<usize> {
+        self.keys().max().copied()
+    }
+}
+
+/// A wrapper which writes a map of index to entry as a table of exactly `len` entries, writing
+/// `default` for any missing index, for block allocation tables and palette slots. Writing
+/// returns an error if an index is out of bounds. Use
+/// `#[binwrite(preprocessor(sparse_table(256, 0xFFFF_FFFFu32)))]` to write a 256 entry table
+/// with unused entries set to `0xFFFFFFFF`.
+pub struct SparseTable<'a, M: ?Sized, T> {
+    entries: &'a M,
+    len: usize,
+    default: T,
+}
+
+impl<'a, M: SparseEntries<T> + ?Sized, T> SparseTable<'a, M, T> {
+    pub fn new(entries: &'a M, len: usize, default: T) -> Self {
+        SparseTable { entries, len, default }
+    }
+}
+
+impl<'a, M: SparseEntries<T> + ?Sized, T: BinWrite> BinWrite for SparseTable<'a, M, T> {
+    fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+        if let Some(index) = self.entries.max_index().filter(|&index| index >= self.len) {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("index {} is out of bounds of a table of {} entries", index, self.len)
+            ));
+        }
+        for index in 0..self.len {
+            let entry = self.entries.entry(index).unwrap_or(&self.default);
+            BinWrite::write_options(entry, writer, options)?;
+        }
+        Ok(())
+    }
+}
+
+/// A preprocessor for writing a map as a fixed-size table. See [SparseTable].
+pub fn sparse_table<M, T>(len: usize, default: T) -> impl Fn(&M) -> SparseTable<'_, M, T>
+    where M: SparseEntries<T> + ?Sized,
+          T: BinWrite + Clone,
+{
+    move |entries| SparseTable::new(entries, len, default.clone())
+}
@@ -0,0 +1,143 @@
+//! # Example:
+//! ```rust
+//! use binwrite::BinWrite;
+//! use binwrite::writers::srec::SRecordFile;
+//!
+//! let mut bytes = vec![];
+//! SRecordFile::from_bytes(b"HDR", 0x1000, &[0x01, 0x02], 16).write(&mut bytes).unwrap();
+//!
+//! assert_eq!(bytes, b"S00600004844521B\nS10510000102E7\nS5030001FB\nS9031000EC\n");
+//! ```
+use super::*;
+use std::io::{Error, ErrorKind};
+use super::intel_hex::write_hex_bytes;
+
+/// The size of the addresses in data and termination records
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AddressSize {
+    /// S1 data records and an S9 termination record
+    Bits16,
+    /// S2 data records and an S8 termination record
+    Bits24,
+    /// S3 data records and an S7 termination record
+    Bits32,
+}
+
+impl AddressSize {
+    /// The smallest address size which can hold `address`
+    pub fn for_address(address: u32) -> Self {
+        if address <= 0xFFFF {
+            AddressSize::Bits16
+        } else if address <= 0xFF_FFFF {
+            AddressSize::Bits24
+        } else {
+            AddressSize::Bits32
+        }
+    }
+
+    fn len(self) -> usize {
+        match self {
+            AddressSize::Bits16 => 2,
+            AddressSize::Bits24 => 3,
+            AddressSize::Bits32 => 4,
+        }
+    }
+}
+
+/// A single line of a Motorola S-record file
+#[derive(Clone, Debug, PartialEq)]
+pub enum SRecord {
+    /// S0 header, usually a module name
+    Header(Vec<u8>),
+    /// S1/S2/S3 data at the given address
+    Data { size: AddressSize, address: u32, data: Vec<u8> },
+    /// S5 count of the data records so far
+    Count(u16),
+    /// S7/S8/S9 termination, holding the address execution starts at
+    Start { size: AddressSize, address: u32 },
+}
+
+impl BinWrite for SRecord {
+    fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+        let (kind, address_len, address, data): (u8, usize, u32, &[u8]) = match self {
+            SRecord::Header(data) => (0, 2, 0, data),
+            SRecord::Data { size, address, data } => {
+                let kind = match size {
+                    AddressSize::Bits16 => 1,
+                    AddressSize::Bits24 => 2,
+                    AddressSize::Bits32 => 3,
+                };
+                (kind, size.len(), *address, data)
+            }
+            SRecord::Count(count) => (5, 2, *count as u32, &[]),
+            SRecord::Start { size, address } => {
+                let kind = match size {
+                    AddressSize::Bits32 => 7,
+                    AddressSize::Bits24 => 8,
+                    AddressSize::Bits16 => 9,
+                };
+                (kind, size.len(), *address, &[])
+            }
+        };
+        if address_len < 4 && address >> (address_len * 8) != 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("address {:#x} does not fit in an S{} record", address, kind)
+            ));
+        }
+        let count = address_len + data.len() + 1;
+        if count > 0xFF {
+            return Err(Error::new(ErrorKind::InvalidData, "S-record is too long"));
+        }
+
+        let mut record = vec![count as u8];
+        record.extend_from_slice(&address.to_be_bytes()[4 - address_len..]);
+        record.extend_from_slice(data);
+        let checksum = !record.iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte));
+        record.push(checksum);
+
+        writer.write_all(&[b'S', b'0' + kind])?;
+        write_hex_bytes(&record, writer, options)?;
+        writer.write_all(b"\n")
+    }
+}
+
+/// A Motorola S-record file, made up of a list of records
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SRecordFile {
+    pub records: Vec<SRecord>,
+}
+
+impl SRecordFile {
+    /// Create the records for `data` loaded at `base_address`, with at most `bytes_per_line` bytes
+    /// per data record. The smallest address size which fits the whole image is used, a count
+    /// record is included if there are no more than 0xFFFF data records and the start address of
+    /// the termination record is `base_address`.
+    pub fn from_bytes(header: &[u8], base_address: u32, data: &[u8], bytes_per_line: usize) -> Self {
+        let last_address = base_address.saturating_add(data.len().saturating_sub(1) as u32);
+        let size = AddressSize::for_address(last_address);
+        let bytes_per_line = bytes_per_line.clamp(1, 0xFF - 1 - size.len());
+
+        let mut records = vec![SRecord::Header(header.to_vec())];
+        records.extend(data.chunks(bytes_per_line).enumerate().map(|(i, chunk)| {
+            SRecord::Data {
+                size,
+                address: base_address.wrapping_add((i * bytes_per_line) as u32),
+                data: chunk.to_vec(),
+            }
+        }));
+        let data_records = records.len() - 1;
+        if data_records <= 0xFFFF {
+            records.push(SRecord::Count(data_records as u16));
+        }
+        records.push(SRecord::Start { size, address: base_address });
+
+        SRecordFile { records }
+    }
+}
+
+impl BinWrite for SRecordFile {
+    fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+        BinWrite::write_options(&self.records, writer, options)
+    }
+}
@@ -0,0 +1,95 @@
+use super::*;
+
+/// A streaming alternative to postprocessors. A postprocessor receives the entire output of a
+/// field as a `Vec<u8>`, while a StreamProcessor sees the output chunk by chunk as it is written,
+/// so checksums and transforms over very large fields don't need to buffer them.
+pub trait StreamProcessor {
+    /// Process a chunk of the field's output, writing the result to `writer`
+    fn process<W: Write>(&mut self, data: &[u8], writer: &mut W) -> Result<()>;
+
+    /// Called after the whole field has been written, for writing any trailing data (such as a
+    /// checksum of the field)
+    fn finish<W: Write>(self, writer: &mut W, options: &WriterOption) -> Result<()>;
+}
+
+/// A writer which passes everything written to it through a [StreamProcessor]
+pub struct ProcessingWriter<'w, W: Write, P: StreamProcessor> {
+    inner: &'w mut W,
+    processor: P,
+}
+
+impl<'w, W: Write, P: StreamProcessor> ProcessingWriter<'w, W, P> {
+    pub fn new(inner: &'w mut W, processor: P) -> Self {
+        ProcessingWriter { inner, processor }
+    }
+
+    /// Finish processing, writing any trailing data
+    pub fn finish(self, options: &WriterOption) -> Result<()> {
+        self.processor.finish(self.inner, options)
+    }
+}
+
+impl<'w, W: Write, P: StreamProcessor> Write for ProcessingWriter<'w, W, P> {
+    fn write(&mut self, data: &[u8]) -> Result<usize> {
+        self.processor.process(data, self.inner)?;
+        Ok(data.len())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// A wrapper which writes the inner value through a [StreamProcessor]. The processor is cloned for
+/// each write, so it should hold the initial state. Use
+/// `#[binwrite(preprocessor(streamed(MyProcessor::new())))]`.
+///
+/// ```rust
+/// use std::io::{Result, Write};
+/// use binwrite::{BinWrite, WriterOption, writers::{streamed, StreamProcessor}};
+///
+/// /// Appends a u8 sum of all bytes written
+/// #[derive(Clone, Default)]
+/// struct AppendSum(u8);
+///
+/// impl StreamProcessor for AppendSum {
+///     fn process<W: Write>(&mut self, data: &[u8], writer: &mut W) -> Result<()> {
+///         self.0 = data.iter().fold(self.0, |sum, byte| sum.wrapping_add(*byte));
+///         writer.write_all(data)
+///     }
+///
+///     fn finish<W: Write>(self, writer: &mut W, options: &WriterOption) -> Result<()> {
+///         self.0.write_options(writer, options)
+///     }
+/// }
+///
+/// let mut bytes = vec![];
+/// (streamed(AppendSum::default()))(&vec![1u8, 2, 3]).write(&mut bytes).unwrap();
+/// assert_eq!(bytes, vec![1, 2, 3, 6]);
+/// ```
+pub struct Streamed<'a, T: ?Sized, P> {
+    value: &'a T,
+    processor: P,
+}
+
+impl<'a, T: ?Sized, P> Streamed<'a, T, P> {
+    pub fn new(value: &'a T, processor: P) -> Self {
+        Streamed { value, processor }
+    }
+}
+
+impl<'a, T: BinWrite + ?Sized, P: StreamProcessor + Clone> BinWrite for Streamed<'a, T, P> {
+    fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+        let mut writer = ProcessingWriter::new(writer, self.processor.clone());
+        BinWrite::write_options(self.value, &mut writer, options)?;
+        writer.finish(options)
+    }
+}
+
+/// A preprocessor for writing a field through a [StreamProcessor]. See [Streamed].
+pub fn streamed<T, P>(processor: P) -> impl Fn(&T) -> Streamed<'_, T, P>
+    where T: BinWrite + ?Sized,
+          P: StreamProcessor + Clone,
+{
+    move |value| Streamed::new(value, processor.clone())
+}
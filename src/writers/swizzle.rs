@@ -0,0 +1,152 @@
+use super::*;
+use std::io::{Error, ErrorKind};
+
+/// A texture layout to reorder a linear (row-major) pixel buffer into when writing
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Swizzle {
+    /// Z-order curve. Rectangular textures are split into square Morton tiles laid out row-major.
+    /// Width and height must be powers of two.
+    Morton { width: usize, height: usize, bytes_per_pixel: usize },
+    /// PSP layout of 16 byte by 8 row tiles laid out row-major. The row length in bytes must be a
+    /// multiple of 16 and the height a multiple of 8.
+    Psp { width: usize, height: usize, bytes_per_pixel: usize },
+    /// PS2 layout of 8-bit (PSMT8) textures. Width must be a multiple of 16 and height a multiple
+    /// of 4.
+    Ps2Psmt8 { width: usize, height: usize },
+    /// Switch (Tegra X1) block-linear layout of 64 byte by 8 row GOBs, grouped into blocks
+    /// `block_height` GOBs tall. Rows and columns are padded out to whole blocks with zeros.
+    BlockLinear { width: usize, height: usize, bytes_per_pixel: usize, block_height: usize },
+}
+
+fn invalid(message: String) -> Error {
+    Error::new(ErrorKind::InvalidInput, message)
+}
+
+/// Spread the bits of `value` out so there is a zero bit between each of them
+fn spread_bits(value: usize) -> usize {
+    (0..usize::BITS as usize / 2).fold(0, |spread, bit| spread | (((value >> bit) & 1) << (bit * 2)))
+}
+
+impl Swizzle {
+    fn dimensions(&self) -> (usize, usize, usize) {
+        match *self {
+            Swizzle::Morton { width, height, bytes_per_pixel }
+            | Swizzle::Psp { width, height, bytes_per_pixel }
+            | Swizzle::BlockLinear { width, height, bytes_per_pixel, .. } => (width, height, bytes_per_pixel),
+            Swizzle::Ps2Psmt8 { width, height } => (width, height, 1),
+        }
+    }
+
+    /// Reorder `linear`, which must hold exactly `width * height` pixels, into this layout
+    pub fn apply(&self, linear: &[u8]) -> Result<Vec<u8>> {
+        let (width, height, bpp) = self.dimensions();
+        if bpp == 0 || linear.len() != width * height * bpp {
+            return Err(invalid(format!(
+                "{:?} needs {} bytes of pixels but got {}", self, width * height * bpp, linear.len()
+            )));
+        }
+
+        match *self {
+            Swizzle::Morton { .. } => {
+                if !width.is_power_of_two() || !height.is_power_of_two() {
+                    return Err(invalid(format!("Morton textures must be a power of two in size, not {}x{}", width, height)));
+                }
+                let tile_bits = width.min(height).trailing_zeros();
+                let mask = (1 << tile_bits) - 1;
+                let tiles_per_row = width >> tile_bits;
+                let mut swizzled = vec![0; linear.len()];
+                for y in 0..height {
+                    for x in 0..width {
+                        let tile = (y >> tile_bits) * tiles_per_row + (x >> tile_bits);
+                        let index = (tile << (2 * tile_bits)) | spread_bits(x & mask) | (spread_bits(y & mask) << 1);
+                        let src = (y * width + x) * bpp;
+                        swizzled[index * bpp..][..bpp].copy_from_slice(&linear[src..src + bpp]);
+                    }
+                }
+                Ok(swizzled)
+            }
+            Swizzle::Psp { .. } => {
+                let row_bytes = width * bpp;
+                if row_bytes % 16 != 0 || height % 8 != 0 {
+                    return Err(invalid(format!("PSP textures must be 16 bytes by 8 rows aligned, not {}x{}", row_bytes, height)));
+                }
+                let mut swizzled = Vec::with_capacity(linear.len());
+                for tile_row in (0..height).step_by(8) {
+                    for tile_col in (0..row_bytes).step_by(16) {
+                        for y in tile_row..tile_row + 8 {
+                            let start = y * row_bytes + tile_col;
+                            swizzled.extend_from_slice(&linear[start..start + 16]);
+                        }
+                    }
+                }
+                Ok(swizzled)
+            }
+            Swizzle::Ps2Psmt8 { .. } => {
+                if width % 16 != 0 || height % 4 != 0 {
+                    return Err(invalid(format!("PSMT8 textures must be 16x4 aligned, not {}x{}", width, height)));
+                }
+                let mut swizzled = vec![0; linear.len()];
+                for y in 0..height {
+                    for x in 0..width {
+                        let block = (y & !0xF) * width + (x & !0xF) * 2;
+                        let swap = (((y + 2) >> 2) & 1) * 4;
+                        let column_y = (((y & !3) >> 1) + (y & 1)) & 7;
+                        let column = column_y * width * 2 + ((x + swap) & 7) * 4;
+                        let byte = ((y >> 1) & 1) + ((x >> 2) & 2);
+                        swizzled[block + column + byte] = linear[y * width + x];
+                    }
+                }
+                Ok(swizzled)
+            }
+            Swizzle::BlockLinear { block_height, .. } => {
+                if !block_height.is_power_of_two() || block_height > 32 {
+                    return Err(invalid(format!("block height must be 1, 2, 4, 8, 16 or 32 GOBs, not {}", block_height)));
+                }
+                let row_bytes = width * bpp;
+                let block_rows = 8 * block_height;
+                let block_size = 512 * block_height;
+                let width_in_gobs = row_bytes.div_ceil(64);
+                let height_in_blocks = height.div_ceil(block_rows);
+                let mut swizzled = vec![0; width_in_gobs * height_in_blocks * block_size];
+                for y in 0..height {
+                    for x in 0..row_bytes {
+                        let gob = (y / block_rows) * block_size * width_in_gobs
+                            + (x / 64) * block_size
+                            + ((y % block_rows) / 8) * 512;
+                        let in_gob = ((x % 64) / 32) * 256
+                            + ((y % 8) / 2) * 64
+                            + ((x % 32) / 16) * 32
+                            + (y % 2) * 16
+                            + (x % 16);
+                        swizzled[gob + in_gob] = linear[y * row_bytes + x];
+                    }
+                }
+                Ok(swizzled)
+            }
+        }
+    }
+}
+
+/// A wrapper which swizzles a linear pixel buffer into a console texture layout when written.
+/// Use `#[binwrite(preprocessor(swizzle(Swizzle::Morton { .. })))]` on a `Vec<u8>`.
+pub struct Swizzled<'a, T: ?Sized> {
+    pixels: &'a T,
+    swizzle: Swizzle,
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Swizzled<'a, T> {
+    pub fn new(pixels: &'a T, swizzle: Swizzle) -> Self {
+        Swizzled { pixels, swizzle }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> BinWrite for Swizzled<'a, T> {
+    fn write_options<W: Write>(&self, writer: &mut W, _options: &WriterOption) -> Result<()> {
+        writer.write_all(&self.swizzle.apply(self.pixels.as_ref())?)
+    }
+}
+
+/// A preprocessor for swizzling texture data. See [Swizzle].
+pub fn swizzle<T: AsRef<[u8]> + ?Sized>(swizzle: Swizzle) -> impl Fn(&T) -> Swizzled<'_, T> {
+    move |pixels| Swizzled::new(pixels, swizzle)
+}
@@ -0,0 +1,168 @@
+//! # Example:
+//! ```rust
+//! use binwrite::writers::tar::{self, TarHeader};
+//!
+//! let mut bytes = vec![];
+//! tar::write_file(&TarHeader::file("hello.txt", 5), b"hello", &mut bytes).unwrap();
+//! tar::write_end(&mut bytes).unwrap();
+//!
+//! assert_eq!(bytes.len(), 512 * 4);
+//! assert_eq!(&bytes[257..263], b"ustar\0");
+//! ```
+use super::*;
+use std::io::{Error, ErrorKind};
+
+/// The size of a tar header and of the blocks file data is padded to
+pub const BLOCK_SIZE: usize = 512;
+
+/// The kind of entry a [TarHeader] describes
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EntryType {
+    File,
+    HardLink,
+    SymLink,
+    Directory,
+}
+
+impl EntryType {
+    fn flag(self) -> u8 {
+        match self {
+            EntryType::File => b'0',
+            EntryType::HardLink => b'1',
+            EntryType::SymLink => b'2',
+            EntryType::Directory => b'5',
+        }
+    }
+}
+
+/// A USTAR header. Numeric fields are written as NUL-terminated octal ASCII and the checksum is
+/// computed over the header when it is written. Paths longer than 100 bytes are split into the
+/// prefix field at a `/`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TarHeader {
+    pub path: String,
+    pub mode: u32,
+    pub uid: u32,
+    pub gid: u32,
+    pub size: u64,
+    /// Modification time in seconds since the Unix epoch
+    pub mtime: u64,
+    pub entry_type: EntryType,
+    /// Target of a hard or symbolic link
+    pub link_name: String,
+    pub user_name: String,
+    pub group_name: String,
+}
+
+impl TarHeader {
+    /// A header for a regular file with mode 0644, owned by root
+    pub fn file<S: Into<String>>(path: S, size: u64) -> Self {
+        TarHeader {
+            path: path.into(),
+            mode: 0o644,
+            uid: 0,
+            gid: 0,
+            size,
+            mtime: 0,
+            entry_type: EntryType::File,
+            link_name: String::new(),
+            user_name: String::from("root"),
+            group_name: String::from("root"),
+        }
+    }
+
+    /// A header for a directory with mode 0755, owned by root
+    pub fn directory<S: Into<String>>(path: S) -> Self {
+        TarHeader {
+            mode: 0o755,
+            entry_type: EntryType::Directory,
+            ..Self::file(path, 0)
+        }
+    }
+
+    /// Split the path into the prefix and name fields
+    fn split_path(&self) -> Result<(&str, &str)> {
+        let path = &self.path[..];
+        if path.len() <= 100 {
+            return Ok(("", path));
+        }
+        path.char_indices()
+            .filter(|(_, c)| *c == '/')
+            .map(|(i, _)| (&path[..i], &path[i + 1..]))
+            .find(|(prefix, name)| prefix.len() <= 155 && name.len() <= 100 && !name.is_empty())
+            .ok_or_else(|| Error::new(ErrorKind::InvalidData, format!("{} is too long for a tar header", path)))
+    }
+
+    /// The header as a 512 byte block
+    pub fn to_block(&self) -> Result<[u8; BLOCK_SIZE]> {
+        let (prefix, name) = self.split_path()?;
+
+        let mut block = [0u8; BLOCK_SIZE];
+        let mut header = &mut block[..];
+        text_field(&mut header, name, 100)?;
+        octal_field(&mut header, self.mode as u64, 8)?;
+        octal_field(&mut header, self.uid as u64, 8)?;
+        octal_field(&mut header, self.gid as u64, 8)?;
+        octal_field(&mut header, self.size, 12)?;
+        octal_field(&mut header, self.mtime, 12)?;
+        // Checksum, treated as spaces while computing the checksum
+        header.write_all(&[b' '; 8])?;
+        header.write_all(&[self.entry_type.flag()])?;
+        text_field(&mut header, &self.link_name, 100)?;
+        header.write_all(b"ustar\x0000")?;
+        text_field(&mut header, &self.user_name, 32)?;
+        text_field(&mut header, &self.group_name, 32)?;
+        octal_field(&mut header, 0, 8)?;
+        octal_field(&mut header, 0, 8)?;
+        text_field(&mut header, prefix, 155)?;
+
+        let checksum: u64 = block.iter().map(|byte| *byte as u64).sum();
+        let mut field = &mut block[148..156];
+        ascii_octal(6)(&checksum).write(&mut field)?;
+        field.write_all(b"\0 ")?;
+        Ok(block)
+    }
+}
+
+impl BinWrite for TarHeader {
+    fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+        match options.timestamp {
+            Some(mtime) => writer.write_all(&TarHeader { mtime, ..self.clone() }.to_block()?),
+            None => writer.write_all(&self.to_block()?),
+        }
+    }
+}
+
+/// Write `text` NUL padded to `width` bytes
+fn text_field<W: Write>(writer: &mut W, text: &str, width: usize) -> Result<()> {
+    if text.len() > width {
+        return Err(Error::new(ErrorKind::InvalidData, format!("{} does not fit in {} bytes", text, width)));
+    }
+    writer.write_all(text.as_bytes())?;
+    pad(writer, width - text.len())
+}
+
+/// Write `value` as zero-padded octal followed by a NUL, `width` bytes in total
+fn octal_field<W: Write>(writer: &mut W, value: u64, width: usize) -> Result<()> {
+    ascii_octal(width - 1)(&value).write(writer)?;
+    writer.write_all(&[0])
+}
+
+/// Write a header followed by the entry's data, padded to a whole number of blocks. Returns an
+/// error if the data's length doesn't match the header's size.
+pub fn write_file<W: Write>(header: &TarHeader, data: &[u8], writer: &mut W) -> Result<()> {
+    if data.len() as u64 != header.size {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!("{} has {:#x} bytes of data but a size of {:#x}", header.path, data.len(), header.size)
+        ));
+    }
+    header.write(writer)?;
+    writer.write_all(data)?;
+    pad(writer, padding_for(data.len() as u64, BLOCK_SIZE)?)
+}
+
+/// Write the two zero blocks marking the end of an archive
+pub fn write_end<W: Write>(writer: &mut W) -> Result<()> {
+    pad(writer, BLOCK_SIZE * 2)
+}
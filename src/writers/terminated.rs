@@ -0,0 +1,42 @@
+use super::*;
+
+/// A wrapper which writes a collection followed by a terminator value, for formats which mark the
+/// end of a collection instead of storing its length. Use
+/// `#[binwrite(preprocessor(terminated(0xFFFFu16)))]` to end a field with a fixed terminator or
+/// `#[binwrite(preprocessor(terminated_with(my_fn)))]` to compute the terminator from the
+/// collection (for example a zeroed entry).
+pub struct Terminated<'a, C: ?Sized, T> {
+    items: &'a C,
+    terminator: T,
+}
+
+impl<'a, C: ?Sized, T> Terminated<'a, C, T> {
+    pub fn new(items: &'a C, terminator: T) -> Self {
+        Terminated { items, terminator }
+    }
+}
+
+impl<'a, C: BinWrite + ?Sized, T: BinWrite> BinWrite for Terminated<'a, C, T> {
+    fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+        BinWrite::write_options(self.items, writer, options)?;
+        BinWrite::write_options(&self.terminator, writer, options)
+    }
+}
+
+/// A preprocessor for writing `terminator` after a collection. See [Terminated].
+pub fn terminated<C, T>(terminator: T) -> impl Fn(&C) -> Terminated<'_, C, T>
+    where C: BinWrite + ?Sized,
+          T: BinWrite + Clone,
+{
+    move |items| Terminated::new(items, terminator.clone())
+}
+
+/// A preprocessor for writing a terminator computed by `terminator` after a collection. See
+/// [Terminated].
+pub fn terminated_with<C, T, F>(terminator: F) -> impl Fn(&C) -> Terminated<'_, C, T>
+    where C: BinWrite + ?Sized,
+          T: BinWrite,
+          F: Fn(&C) -> T,
+{
+    move |items| Terminated::new(items, terminator(items))
+}
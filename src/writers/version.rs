@@ -0,0 +1,33 @@
+use super::*;
+
+/// The version of a type's wire format, for detecting format drift between library versions at
+/// runtime. Bump it whenever the type's written layout changes. A `format_version` derive
+/// attribute would implement this; until then, implement it next to the derive.
+pub trait FormatVersion {
+    const FORMAT_VERSION: u32;
+}
+
+/// A wrapper which writes the inner value's [FORMAT_VERSION](FormatVersion::FORMAT_VERSION) as a
+/// u32 header field before the value, using the endianness. Use
+/// `#[binwrite(preprocessor(versioned))]`.
+pub struct Versioned<'a, T: ?Sized> {
+    value: &'a T,
+}
+
+impl<'a, T: FormatVersion + ?Sized> Versioned<'a, T> {
+    pub fn new(value: &'a T) -> Self {
+        Versioned { value }
+    }
+}
+
+impl<'a, T: FormatVersion + BinWrite + ?Sized> BinWrite for Versioned<'a, T> {
+    fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+        BinWrite::write_options(&T::FORMAT_VERSION, writer, options)?;
+        BinWrite::write_options(self.value, writer, options)
+    }
+}
+
+/// A preprocessor for writing a value preceded by its format version. See [Versioned].
+pub fn versioned<T: FormatVersion + ?Sized>(value: &T) -> Versioned<'_, T> {
+    Versioned::new(value)
+}
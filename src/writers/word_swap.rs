@@ -0,0 +1,95 @@
+use super::*;
+
+/// How [WordSwap] rearranges the bytes of each primitive
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WordOrder {
+    /// The two halves of the primitive are swapped, see [word_swapped]
+    Swapped,
+    /// The 16-bit words of the little endian primitive are written most significant first, see
+    /// [pdp_endian]
+    Pdp,
+}
+
+/// Types which can be written with the words of each primitive rearranged. Collections rearrange
+/// each item separately, so words never move across item boundaries.
+pub trait WordSwap {
+    fn write_word_order<W: Write>(&self, writer: &mut W, options: &WriterOption, order: WordOrder) -> Result<()>;
+}
+
+/// Internal macro for implementing WordSwap for primitives
+macro_rules! word_swap_impl {
+    ($($type_name:ty),*$(,)?) => {
+        $(
+            impl WordSwap for $type_name {
+                fn write_word_order<W: Write>(&self, writer: &mut W, options: &WriterOption, order: WordOrder) -> Result<()> {
+                    let mut bytes = [0u8; std::mem::size_of::<$type_name>()];
+                    BinWrite::write_options(self, &mut &mut bytes[..], options)?;
+                    let half = bytes.len() / 2;
+                    match order {
+                        WordOrder::Swapped => bytes.rotate_left(half),
+                        WordOrder::Pdp => {
+                            bytes.reverse();
+                            for word in bytes.chunks_exact_mut(2) {
+                                word.swap(0, 1);
+                            }
+                        }
+                    }
+                    writer.write_all(&bytes)
+                }
+            }
+        )*
+    }
+}
+
+word_swap_impl!(u16, u32, u64, u128, i16, i32, i64, i128, f32, f64);
+
+impl<T: WordSwap> WordSwap for [T] {
+    fn write_word_order<W: Write>(&self, writer: &mut W, options: &WriterOption, order: WordOrder) -> Result<()> {
+        for item in self {
+            item.write_word_order(writer, options, order)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: WordSwap> WordSwap for Vec<T> {
+    fn write_word_order<W: Write>(&self, writer: &mut W, options: &WriterOption, order: WordOrder) -> Result<()> {
+        self[..].write_word_order(writer, options, order)
+    }
+}
+
+macro_rules! word_swap_array_impl {
+    ($($size:literal),*$(,)?) => {
+        $(
+            impl<T: WordSwap> WordSwap for [T; $size] {
+                fn write_word_order<W: Write>(&self, writer: &mut W, options: &WriterOption, order: WordOrder) -> Result<()> {
+                    self[..].write_word_order(writer, options, order)
+                }
+            }
+        )*
+    }
+}
+
+word_swap_array_impl!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20);
+
+/// A built in writer for word-swapped values: each primitive is written using the current
+/// endianness and then its two halves are swapped, so a u64 is written as its low u32 followed by
+/// its high u32 when big endian. Use `#[binwrite(with(writers::word_swapped))]`.
+pub fn word_swapped<T, W>(value: &T, writer: &mut W, options: &WriterOption) -> Result<()>
+    where T: WordSwap + ?Sized,
+          W: Write,
+{
+    value.write_word_order(writer, options, WordOrder::Swapped)
+}
+
+/// A built in writer for PDP-11 style middle-endian values, where 16-bit words are little endian
+/// but the most significant word of each primitive comes first (0x0A0B0C0D is written as
+/// 0B 0A 0D 0C). Ignores the current endianness. Use `#[binwrite(with(writers::pdp_endian))]`.
+pub fn pdp_endian<T, W>(value: &T, writer: &mut W, options: &WriterOption) -> Result<()>
+    where T: WordSwap + ?Sized,
+          W: Write,
+{
+    let mut options = options.clone();
+    options.endian = Endian::Little;
+    value.write_word_order(writer, &options, WordOrder::Pdp)
+}
@@ -0,0 +1,176 @@
+use super::*;
+use std::collections::HashMap;
+
+const WINDOW: usize = 0x1000;
+const MIN_MATCH: usize = 3;
+const MAX_MATCH: usize = 0x111;
+/// Matches at least this long need an extra length byte
+const LONG_MATCH: usize = 0x12;
+/// How many earlier positions to try per byte, trading compression for speed
+const MAX_CANDIDATES: usize = 64;
+
+/// A Nintendo LZ compression format
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LzFormat {
+    /// Yaz0, used by SZS files on GameCube, Wii, Wii U and Switch
+    Yaz0,
+    /// Yay0 (also known as SZP), used on N64 and GameCube, which stores flags, back-references
+    /// and literals in separate streams
+    Yay0,
+}
+
+enum Token {
+    Literal(u8),
+    /// A copy of `len` bytes from `dist` bytes back
+    Match { dist: usize, len: usize },
+}
+
+/// Greedily split `data` into literals and back-references
+fn tokenize(data: &[u8]) -> Vec<Token> {
+    let mut tokens = vec![];
+    let mut positions: HashMap<[u8; 3], Vec<usize>> = HashMap::new();
+    let insert = |positions: &mut HashMap<[u8; 3], Vec<usize>>, pos: usize| {
+        if pos + MIN_MATCH <= data.len() {
+            positions.entry([data[pos], data[pos + 1], data[pos + 2]]).or_default().push(pos);
+        }
+    };
+
+    let mut pos = 0;
+    while pos < data.len() {
+        let mut best = (0, 0);
+        if pos + MIN_MATCH <= data.len() {
+            let key = [data[pos], data[pos + 1], data[pos + 2]];
+            let max_len = MAX_MATCH.min(data.len() - pos);
+            let candidates = positions.get(&key).map(|candidates| &candidates[..]).unwrap_or(&[]);
+            for &start in candidates.iter().rev().take(MAX_CANDIDATES) {
+                if pos - start > WINDOW {
+                    break;
+                }
+                let len = (0..max_len).take_while(|i| data[start + i] == data[pos + i]).count();
+                if len > best.1 {
+                    best = (pos - start, len);
+                }
+            }
+        }
+
+        let (dist, len) = best;
+        if len >= MIN_MATCH {
+            tokens.push(Token::Match { dist, len });
+            for i in pos..pos + len {
+                insert(&mut positions, i);
+            }
+            pos += len;
+        } else {
+            tokens.push(Token::Literal(data[pos]));
+            insert(&mut positions, pos);
+            pos += 1;
+        }
+    }
+    tokens
+}
+
+impl LzFormat {
+    /// Compress `data`, including the format's header
+    pub fn compress(&self, data: &[u8]) -> Result<Vec<u8>> {
+        let size: u32 = len_as(data.len())?;
+        let tokens = tokenize(data);
+        match self {
+            LzFormat::Yaz0 => Ok(compress_yaz0(size, &tokens)),
+            LzFormat::Yay0 => compress_yay0(size, &tokens),
+        }
+    }
+}
+
+fn compress_yaz0(size: u32, tokens: &[Token]) -> Vec<u8> {
+    let mut out = b"Yaz0".to_vec();
+    out.extend_from_slice(&size.to_be_bytes());
+    out.extend_from_slice(&[0; 8]);
+
+    for group in tokens.chunks(8) {
+        let flags_pos = out.len();
+        out.push(0);
+        for (i, token) in group.iter().enumerate() {
+            match *token {
+                Token::Literal(byte) => {
+                    out[flags_pos] |= 0x80 >> i;
+                    out.push(byte);
+                }
+                Token::Match { dist, len } => {
+                    let dist = dist - 1;
+                    if len >= LONG_MATCH {
+                        out.extend_from_slice(&[(dist >> 8) as u8, dist as u8, (len - LONG_MATCH) as u8]);
+                    } else {
+                        out.extend_from_slice(&[(((len - 2) << 4) | (dist >> 8)) as u8, dist as u8]);
+                    }
+                }
+            }
+        }
+    }
+    out
+}
+
+fn compress_yay0(size: u32, tokens: &[Token]) -> Result<Vec<u8>> {
+    let mut masks = vec![];
+    let mut links = vec![];
+    let mut chunks = vec![];
+    for group in tokens.chunks(32) {
+        let mut mask = 0u32;
+        for (i, token) in group.iter().enumerate() {
+            match *token {
+                Token::Literal(byte) => {
+                    mask |= 0x8000_0000 >> i;
+                    chunks.push(byte);
+                }
+                Token::Match { dist, len } => {
+                    let dist = dist - 1;
+                    if len >= LONG_MATCH {
+                        links.extend_from_slice(&(dist as u16).to_be_bytes());
+                        chunks.push((len - LONG_MATCH) as u8);
+                    } else {
+                        links.extend_from_slice(&((((len - 2) << 12) | dist) as u16).to_be_bytes());
+                    }
+                }
+            }
+        }
+        masks.extend_from_slice(&mask.to_be_bytes());
+    }
+
+    let links_offset = 0x10 + masks.len();
+    let chunks_offset = links_offset + links.len();
+    let mut out = b"Yay0".to_vec();
+    out.extend_from_slice(&size.to_be_bytes());
+    out.extend_from_slice(&len_as::<u32>(links_offset)?.to_be_bytes());
+    out.extend_from_slice(&len_as::<u32>(chunks_offset)?.to_be_bytes());
+    out.extend_from_slice(&masks);
+    out.extend_from_slice(&links);
+    out.extend_from_slice(&chunks);
+    Ok(out)
+}
+
+/// Bytes which are compressed when written. See [LzFormat].
+pub struct LzCompressed {
+    bytes: Vec<u8>,
+    format: LzFormat,
+}
+
+impl LzCompressed {
+    pub fn new(bytes: Vec<u8>, format: LzFormat) -> Self {
+        LzCompressed { bytes, format }
+    }
+}
+
+impl BinWrite for LzCompressed {
+    fn write_options<W: Write>(&self, writer: &mut W, _options: &WriterOption) -> Result<()> {
+        writer.write_all(&self.format.compress(&self.bytes)?)
+    }
+}
+
+/// A postprocessor for Yaz0 compressing a field. Use `#[binwrite(postprocessor(yaz0))]`.
+pub fn yaz0(bytes: Vec<u8>) -> LzCompressed {
+    LzCompressed::new(bytes, LzFormat::Yaz0)
+}
+
+/// A postprocessor for Yay0 compressing a field. Use `#[binwrite(postprocessor(yay0))]`.
+pub fn yay0(bytes: Vec<u8>) -> LzCompressed {
+    LzCompressed::new(bytes, LzFormat::Yay0)
+}
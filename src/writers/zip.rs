@@ -0,0 +1,206 @@
+//! # Example:
+//! ```rust
+//! use binwrite::BinWrite;
+//! use binwrite::writers::zip::ZipArchive;
+//!
+//! let mut archive = ZipArchive::new();
+//! archive.add_stored("hello.txt", b"Hello, world!".to_vec());
+//! archive.add_stored("empty/", vec![]);
+//!
+//! let mut bytes = vec![];
+//! archive.write(&mut bytes).unwrap();
+//!
+//! assert_eq!(&bytes[..4], b"PK\x03\x04");
+//! assert_eq!(&bytes[bytes.len() - 22..][..4], b"PK\x05\x06");
+//! ```
+use super::*;
+use crate::checksum::Crc;
+use std::convert::TryFrom;
+use std::io::{Error, ErrorKind};
+
+const LOCAL_FILE_HEADER_SIGNATURE: u32 = 0x0403_4B50;
+const CENTRAL_DIRECTORY_SIGNATURE: u32 = 0x0201_4B50;
+const END_OF_CENTRAL_DIRECTORY_SIGNATURE: u32 = 0x0605_4B50;
+
+/// The version of the ZIP specification needed to extract (2.0, for deflate and directories)
+const VERSION: u16 = 20;
+/// General purpose flag marking file names as UTF-8
+const UTF8_FLAG: u16 = 1 << 11;
+
+/// How an entry's data is compressed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Method {
+    Stored = 0,
+    Deflated = 8,
+}
+
+/// A single file in a [ZipArchive]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ZipEntry {
+    pub name: String,
+    pub method: Method,
+    /// The entry's data, compressed according to `method`
+    pub data: Vec<u8>,
+    /// CRC-32 of the uncompressed data
+    pub crc32: u32,
+    pub uncompressed_size: u32,
+    /// MS-DOS modification time and date, defaulting to 1980-01-01 00:00. See [dos_datetime].
+    pub modified: (u16, u16),
+}
+
+impl ZipEntry {
+    fn flags(&self) -> u16 {
+        if self.name.is_ascii() { 0 } else { UTF8_FLAG }
+    }
+
+    fn compressed_size(&self) -> Result<u32> {
+        u32::try_from(self.data.len())
+            .map_err(|_| Error::new(ErrorKind::InvalidData, format!("{} is too large for a zip without zip64", self.name)))
+    }
+
+    fn name_len(&self) -> Result<u16> {
+        len_as(self.name.len())
+    }
+
+    /// The modification time, or the fixed timestamp from the options if there is one
+    fn modified(&self, options: &WriterOption) -> (u16, u16) {
+        options.timestamp.map_or(self.modified, dos_datetime)
+    }
+}
+
+/// Convert seconds since the Unix epoch to an MS-DOS (time, date) pair, clamped to the range DOS
+/// dates can represent (1980 to 2107)
+pub fn dos_datetime(unix: u64) -> (u16, u16) {
+    const DOS_EPOCH: u64 = 315_532_800;
+    let secs = unix.max(DOS_EPOCH);
+    let (days, secs) = ((secs / 86400) as i64, secs % 86400);
+
+    // Days since the Unix epoch to a civil date, from Howard Hinnant's date algorithms
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    if year > 2107 {
+        return (0xBF7D, 0xFF9F);
+    }
+    let time = ((secs / 3600) << 11) | ((secs % 3600 / 60) << 5) | ((secs % 60) / 2);
+    let date = ((year - 1980) << 9) | (month << 5) | day;
+    (time as u16, date as u16)
+}
+
+/// The header written before each entry's data
+pub struct LocalFileHeader<'a>(pub &'a ZipEntry);
+
+impl<'a> BinWrite for LocalFileHeader<'a> {
+    fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+        let entry = self.0;
+        let (time, date) = entry.modified(options);
+        (
+            LOCAL_FILE_HEADER_SIGNATURE, VERSION, entry.flags(), entry.method as u16, time, date,
+            entry.crc32, entry.compressed_size()?, entry.uncompressed_size, entry.name_len()?, 0u16,
+            &entry.name[..],
+        ).write_options(writer, options)
+    }
+}
+
+/// The record for a single entry in the central directory
+pub struct CentralDirectoryHeader<'a> {
+    pub entry: &'a ZipEntry,
+    /// Offset of the entry's local file header from the start of the archive
+    pub offset: u32,
+}
+
+impl<'a> BinWrite for CentralDirectoryHeader<'a> {
+    fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+        let entry = self.entry;
+        let (time, date) = entry.modified(options);
+        (
+            CENTRAL_DIRECTORY_SIGNATURE, VERSION, VERSION, entry.flags(), entry.method as u16, time,
+            date, entry.crc32, entry.compressed_size()?, entry.uncompressed_size, entry.name_len()?,
+            0u16, 0u16, 0u16, 0u16, 0u32, self.offset, &entry.name[..],
+        ).write_options(writer, options)
+    }
+}
+
+/// The record at the end of a ZIP file describing where the central directory is
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EndOfCentralDirectory {
+    pub entries: u16,
+    pub size: u32,
+    pub offset: u32,
+}
+
+impl BinWrite for EndOfCentralDirectory {
+    fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+        (
+            END_OF_CENTRAL_DIRECTORY_SIGNATURE, 0u16, 0u16, self.entries, self.entries, self.size,
+            self.offset, 0u16,
+        ).write_options(writer, options)
+    }
+}
+
+/// A ZIP archive, written as each entry's local file header and data followed by the central
+/// directory. Zip64 is not supported, so entries and the archive are limited to 4 GiB. ZIP is
+/// always little endian, regardless of the options.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ZipArchive {
+    pub entries: Vec<ZipEntry>,
+}
+
+impl ZipArchive {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an uncompressed entry. Directories are entries whose name ends with `/`.
+    pub fn add_stored<S: Into<String>>(&mut self, name: S, data: Vec<u8>) {
+        let crc32 = Crc::CRC32.checksum(&data) as u32;
+        let uncompressed_size = data.len() as u32;
+        self.entries.push(ZipEntry {
+            name: name.into(), method: Method::Stored, data, crc32, uncompressed_size, modified: (0, 0x21),
+        });
+    }
+
+    /// Add an entry which has already been compressed as a raw deflate stream, along with the
+    /// CRC-32 and size of the uncompressed data
+    pub fn add_deflated<S: Into<String>>(&mut self, name: S, compressed: Vec<u8>, crc32: u32, uncompressed_size: u32) {
+        self.entries.push(ZipEntry {
+            name: name.into(), method: Method::Deflated, data: compressed, crc32, uncompressed_size, modified: (0, 0x21),
+        });
+    }
+}
+
+impl BinWrite for ZipArchive {
+    fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+        let options = WriterOption {
+            endian: Endian::Little,
+            ..options.clone()
+        };
+
+        let mut archive = vec![];
+        let mut offsets = Vec::with_capacity(self.entries.len());
+        for entry in &self.entries {
+            offsets.push(len_as::<u32>(archive.len())?);
+            LocalFileHeader(entry).write_options(&mut archive, &options)?;
+            archive.extend_from_slice(&entry.data);
+        }
+
+        let directory_offset = len_as::<u32>(archive.len())?;
+        for (entry, offset) in self.entries.iter().zip(offsets) {
+            CentralDirectoryHeader { entry, offset }.write_options(&mut archive, &options)?;
+        }
+        EndOfCentralDirectory {
+            entries: len_as(self.entries.len())?,
+            size: len_as(archive.len() - directory_offset as usize)?,
+            offset: directory_offset,
+        }.write_options(&mut archive, &options)?;
+
+        writer.write_all(&archive)
+    }
+}
@@ -0,0 +1,73 @@
+#[cfg(feature = "smallvec")]
+#[test]
+fn smallvec() {
+    use binwrite::BinWrite;
+
+    let mut bytes = vec![];
+    let small: smallvec::SmallVec<[u16; 4]> = smallvec::smallvec![1, 2];
+    small.write_options(&mut bytes, &binwrite::writer_option_new!(endian: binwrite::Endian::Big)).unwrap();
+    assert_eq!(bytes, vec![0, 1, 0, 2]);
+}
+
+#[cfg(feature = "arrayvec")]
+#[test]
+fn arrayvec() {
+    use binwrite::BinWrite;
+
+    let mut bytes = vec![];
+    let mut array = arrayvec::ArrayVec::<u16, 4>::new();
+    array.push(1);
+    array.push(2);
+    array.write_options(&mut bytes, &binwrite::writer_option_new!(endian: binwrite::Endian::Big)).unwrap();
+    assert_eq!(bytes, vec![0, 1, 0, 2]);
+}
+
+#[cfg(feature = "heapless")]
+#[test]
+fn heapless() {
+    use binwrite::BinWrite;
+
+    let mut bytes = vec![];
+    let mut vec = heapless::Vec::<u16, 4>::new();
+    vec.push(1).unwrap();
+    vec.push(2).unwrap();
+    vec.write_options(&mut bytes, &binwrite::writer_option_new!(endian: binwrite::Endian::Big)).unwrap();
+    assert_eq!(bytes, vec![0, 1, 0, 2]);
+}
+
+#[cfg(feature = "bytes")]
+#[test]
+fn bytes() {
+    use binwrite::BinWrite;
+
+    let mut out = vec![];
+    let payload = bytes::Bytes::from_static(b"payload");
+    let mut frame = bytes::BytesMut::new();
+    frame.extend_from_slice(b"frame");
+    (7u8, payload, frame).write(&mut out).unwrap();
+    assert_eq!(out, b"\x07payloadframe");
+}
+
+#[cfg(feature = "mint")]
+#[test]
+fn mint() {
+    use binwrite::BinWrite;
+
+    let mut bytes = vec![];
+    let position = mint::Vector3 { x: 1u16, y: 2, z: 3 };
+    let rotation = mint::Quaternion { v: mint::Vector3 { x: 4u16, y: 5, z: 6 }, s: 7 };
+    (position, rotation).write_options(&mut bytes, &binwrite::writer_option_new!(endian: binwrite::Endian::Big)).unwrap();
+    assert_eq!(bytes, vec![0, 1, 0, 2, 0, 3, 0, 4, 0, 5, 0, 6, 0, 7]);
+}
+
+#[cfg(feature = "nalgebra")]
+#[test]
+fn nalgebra() {
+    use binwrite::BinWrite;
+
+    let mut bytes = vec![];
+    let matrix = nalgebra::Matrix2::new(1u8, 2, 3, 4);
+    let vector = nalgebra::Vector3::new(5u8, 6, 7);
+    (matrix, vector).write(&mut bytes).unwrap();
+    assert_eq!(bytes, vec![1, 3, 2, 4, 5, 6, 7]);
+}
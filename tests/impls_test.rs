@@ -0,0 +1,244 @@
+use binwrite::BinWrite;
+
+#[test]
+fn references() {
+    let vertices = [(1u8, 2u8), (3, 4)];
+    let borrowed: &[(u8, u8)] = &vertices;
+    let mut value = 5u8;
+
+    let mut bytes = vec![];
+    (borrowed, &mut value, &b"ab"[..], "cd").write(&mut bytes).unwrap();
+    assert_eq!(bytes, vec![1, 2, 3, 4, 5, b'a', b'b', b'c', b'd']);
+}
+
+#[test]
+fn explicit_endian() {
+    use binwrite::endian::{U16Be, U32Le, F32Be};
+
+    let mut bytes = vec![];
+    (U16Be(1), U32Le(2), F32Be(1.0), 3u16)
+        .write_options(&mut bytes, &binwrite::writer_option_new!(endian: binwrite::Endian::Big))
+        .unwrap();
+    assert_eq!(bytes, vec![0, 1, 2, 0, 0, 0, 0x3F, 0x80, 0, 0, 0, 3]);
+}
+
+#[test]
+fn aligned_items() {
+    use binwrite::WriterOption;
+    use std::io::{Result, Write};
+
+    struct Record(u8, u16);
+
+    impl BinWrite for Record {
+        const ALIGNMENT: usize = 4;
+
+        fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+            (self.0, self.1).write_options(writer, options)
+        }
+    }
+
+    let mut bytes = vec![];
+    vec![Record(1, 2), Record(3, 4)]
+        .write_options(&mut bytes, &binwrite::writer_option_new!(endian: binwrite::Endian::Big))
+        .unwrap();
+    assert_eq!(bytes, vec![1, 0, 2, 0, 3, 0, 4, 0]);
+}
+
+#[test]
+fn deny_native() {
+    let options = binwrite::writer_option_new!(deny_native: true);
+    let mut bytes = vec![];
+    assert!(1u32.write_options(&mut bytes, &options).is_err());
+    assert!(1u8.write_options(&mut bytes, &options).is_ok());
+    assert!(1u32.write(&mut bytes).is_ok());
+    let options = binwrite::writer_option_new!(endian: binwrite::Endian::Big, deny_native: true);
+    assert!(1u32.write_options(&mut bytes, &options).is_ok());
+}
+
+#[test]
+fn phantom_data() {
+    use binwrite::FixedSize;
+    use std::marker::PhantomData;
+
+    let mut bytes = vec![];
+    (1u8, PhantomData::<fn() -> String>, 2u8).write(&mut bytes).unwrap();
+    assert_eq!(bytes, vec![1, 2]);
+    assert_eq!(<(u8, PhantomData<str>) as FixedSize>::SIZE, 1);
+}
+
+#[test]
+fn smart_pointers() {
+    use std::borrow::Cow;
+    use std::rc::Rc;
+    use std::sync::Arc;
+
+    let boxed: Box<[u8]> = Box::new([1, 2]);
+    let borrowed: Cow<[u8]> = Cow::Borrowed(&[3]);
+    let owned: Cow<str> = Cow::Owned(String::from("a"));
+
+    let mut bytes = vec![];
+    (boxed, borrowed, owned, Rc::new(4u8), Arc::new(5u8)).write(&mut bytes).unwrap();
+    assert_eq!(bytes, vec![1, 2, 3, b'a', 4, 5]);
+}
+
+#[test]
+fn write_limited() {
+    let options = binwrite::writer_option_new!(endian: binwrite::Endian::Little, max_size: Some(4));
+
+    let mut bytes = vec![];
+    (1u16, 2u16).write_limited(&mut bytes, &options).unwrap();
+    assert_eq!(bytes.len(), 4);
+
+    let mut bytes = vec![];
+    let err = (1u16, 2u16, 3u8).write_limited(&mut bytes, &options).unwrap_err();
+    assert!(err.to_string().contains("maximum size of 4 bytes"));
+    assert_eq!(bytes.len(), 4);
+
+    let mut bytes = vec![];
+    vec![0u8; 16].write_limited(&mut bytes, &Default::default()).unwrap();
+    assert_eq!(bytes.len(), 16);
+}
+
+#[test]
+fn write_into_cursor() {
+    use binwrite::{write_into_cursor, OutputTooSmall};
+    use std::io::Cursor;
+
+    let mut buf = [0u8; 3];
+    let mut cursor = Cursor::new(&mut buf[..]);
+    assert_eq!(write_into_cursor(&(1u8, 2u8), &mut cursor, &Default::default()).unwrap(), 2);
+
+    let error = write_into_cursor(&[3u8, 4], &mut cursor, &Default::default()).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::WriteZero);
+    assert_eq!(OutputTooSmall::from_io(&error), Some(&OutputTooSmall { needed: 2, available: 1 }));
+    assert!(error.to_string().contains("needed 2 bytes"));
+    assert_eq!(buf, [1, 2, 0]);
+}
+
+#[test]
+fn write_options_reuse() {
+    use binwrite::WriteScratch;
+    use binwrite::checksum::{crc_appended, Crc};
+    use std::sync::Arc;
+
+    let scratch = Arc::new(WriteScratch::new());
+    let options = binwrite::writer_option_new!(endian: binwrite::Endian::Big);
+    let mut frames = vec![];
+    for frame in 0..4u8 {
+        (crc_appended(Crc::CRC8))(&[frame; 16]).write_options_reuse(&mut frames, &options, &scratch).unwrap();
+    }
+    assert_eq!(frames.len(), 4 * 17);
+    assert_eq!(scratch.pooled(), 1);
+    assert!(scratch.take().capacity() >= 16);
+}
+
+#[test]
+fn manual_impl_macro() {
+    struct Entry {
+        id: u16,
+        data: Vec<u8>,
+    }
+
+    binwrite::binwrite_manual_impl!(Entry, {
+        id,
+        pad(1),
+        data,
+        align(4),
+    });
+
+    let mut bytes = vec![];
+    Entry { id: 1, data: vec![7] }
+        .write_options(&mut bytes, &binwrite::writer_option_new!(endian: binwrite::Endian::Big))
+        .unwrap();
+    assert_eq!(bytes, vec![0, 1, 0, 7]);
+
+    let mut bytes = vec![];
+    vec![Entry { id: 2, data: vec![1, 2] }]
+        .write_options(&mut bytes, &binwrite::writer_option_new!(endian: binwrite::Endian::Little))
+        .unwrap();
+    assert_eq!(bytes, vec![2, 0, 0, 1, 2, 0, 0, 0]);
+}
+
+#[test]
+fn manual_impl_field_groups() {
+    struct Archive {
+        magic: [u8; 4],
+        count: u8,
+        files: Vec<u8>,
+    }
+
+    binwrite::binwrite_manual_impl!(Archive, fn write_header, { magic, count });
+    binwrite::binwrite_manual_impl!(Archive, fn write_body, { files, align(4) });
+
+    let archive = Archive { magic: *b"ARC0", count: 1, files: vec![9] };
+    let options = binwrite::WriterOption::default();
+    let mut body = vec![];
+    archive.write_body(&mut body, &options).unwrap();
+    let mut header = vec![];
+    archive.write_header(&mut header, &options).unwrap();
+    assert_eq!(header, b"ARC0\x01");
+    assert_eq!(body, vec![9, 0, 0, 0]);
+}
+
+#[test]
+fn manual_impl_trim_default() {
+    struct Record {
+        id: u8,
+        flags: u8,
+        scale: u8,
+    }
+
+    binwrite::binwrite_manual_impl!(Record, { id, trim_default(flags, scale) });
+
+    let mut bytes = vec![];
+    Record { id: 1, flags: 0, scale: 0 }.write(&mut bytes).unwrap();
+    Record { id: 2, flags: 3, scale: 0 }.write(&mut bytes).unwrap();
+    Record { id: 4, flags: 0, scale: 5 }.write(&mut bytes).unwrap();
+    assert_eq!(bytes, vec![1, 2, 3, 4, 0, 5]);
+}
+
+#[test]
+fn canonical_maps() {
+    use std::collections::{BTreeMap, HashMap, HashSet};
+
+    let options = binwrite::writer_option_new!(endian: binwrite::Endian::Big, canonical: true);
+    let map: HashMap<u16, u8> = (0..32).map(|i| (i * 7 % 32, i as u8)).collect();
+    let mut bytes = vec![];
+    map.write_options(&mut bytes, &options).unwrap();
+    let sorted: BTreeMap<u16, u8> = map.into_iter().collect();
+    let mut expected = vec![];
+    sorted.write_options(&mut expected, &options).unwrap();
+    assert_eq!(bytes, expected);
+
+    let set: HashSet<u8> = (0..32).rev().collect();
+    let mut bytes = vec![];
+    set.write_options(&mut bytes, &options).unwrap();
+    assert_eq!(bytes, (0..32).collect::<Vec<u8>>());
+}
+
+#[test]
+fn byte_slices_write_at_once() {
+    use std::io::{Result, Write};
+
+    struct CountWrites(usize);
+
+    impl Write for CountWrites {
+        fn write(&mut self, data: &[u8]) -> Result<usize> {
+            self.0 += 1;
+            Ok(data.len())
+        }
+
+        fn flush(&mut self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    let bytes = vec![1u8; 64];
+    let slice: &[u8] = &bytes;
+    let mut writer = CountWrites(0);
+    bytes.write(&mut writer).unwrap();
+    slice.write(&mut writer).unwrap();
+    (&slice).write(&mut writer).unwrap();
+    [0u8; 16].write(&mut writer).unwrap();
+    assert_eq!(writer.0, 4);
+}
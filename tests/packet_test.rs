@@ -0,0 +1,54 @@
+use binwrite::packet::{internet_checksum, FieldSize, PacketBuilder, Scope};
+
+#[test]
+fn ipv4_header_checksum() {
+    let header = [
+        0x45, 0x00, 0x00, 0x73, 0x00, 0x00, 0x40, 0x00, 0x40, 0x11, 0x00, 0x00, 0xC0, 0xA8, 0x00,
+        0x01, 0xC0, 0xA8, 0x00, 0xC7,
+    ];
+    assert_eq!(internet_checksum(&header), 0xB861);
+
+    let mut packet = PacketBuilder::new();
+    packet.push(&header[..]).unwrap().checksum(10, Scope::Header, None);
+    assert_eq!(&packet.build().unwrap()[10..12], &[0xB8, 0x61]);
+}
+
+#[test]
+fn field_out_of_bounds() {
+    let mut packet = PacketBuilder::new();
+    packet.push(&0u16).unwrap().length(1, FieldSize::U16, Scope::Header);
+    assert!(packet.build().is_err());
+}
+
+#[test]
+fn udp_zero_checksum() {
+    use binwrite::packet::PseudoHeader;
+
+    let pseudo_header = PseudoHeader::Ipv4 { src: [10, 0, 0, 1], dst: [10, 0, 0, 2], protocol: 17 };
+    let udp = |payload: &[u8]| {
+        let mut packet = PacketBuilder::new();
+        packet.push(&(1234u16, 5678u16, 0u16, 0u16)).unwrap()
+            .length(4, FieldSize::U16, Scope::WholeLayer)
+            .checksum(6, Scope::WholeLayer, Some(pseudo_header));
+        packet.payload(payload);
+        packet.build().unwrap()
+    };
+
+    // Using the checksum as the payload makes the ones' complement sum 0xFFFF, so the computed
+    // checksum is 0, which UDP sends as 0xFFFF
+    let checksum = udp(&[0, 0])[6..8].to_vec();
+    assert_eq!(&udp(&checksum)[6..8], &[0xFF, 0xFF]);
+}
+
+#[test]
+fn pseudo_header_length_overflow() {
+    use binwrite::packet::PseudoHeader;
+
+    let pseudo_header = PseudoHeader::Ipv4 { src: [10, 0, 0, 1], dst: [10, 0, 0, 2], protocol: 17 };
+    assert!(pseudo_header.to_bytes(0x10000).is_err());
+
+    let mut packet = PacketBuilder::new();
+    packet.push(&[0u8; 8]).unwrap().checksum(6, Scope::WholeLayer, Some(pseudo_header));
+    packet.payload(&vec![0; 0x10000]);
+    assert!(packet.build().is_err());
+}
@@ -0,0 +1,56 @@
+use binwrite::checksum::Crc;
+use binwrite::record::{Framing, RecordStream};
+
+#[test]
+fn record_framing() {
+    let options = binwrite::writer_option_new!(endian: binwrite::Endian::Little);
+    let mut stream = RecordStream::new(vec![], Framing::LENGTH_CRC32, options);
+    assert_eq!(stream.write(0, &0x0201u16).unwrap(), 0);
+    assert_eq!(stream.write(0, &()).unwrap(), 10);
+    assert_eq!(stream.count(), 2);
+
+    let bytes = stream.into_inner();
+    let crc = Crc::CRC32.checksum(&[2, 0, 0, 0, 1, 2]) as u32;
+    assert_eq!(&bytes[..6], &[2, 0, 0, 0, 1, 2]);
+    assert_eq!(&bytes[6..10], &crc.to_le_bytes());
+    assert_eq!(bytes.len(), 18);
+}
+
+#[test]
+fn record_tag_overflow() {
+    let mut stream = RecordStream::new(vec![], Framing::new(1, 0, None), Default::default());
+    assert!(stream.write(0x100, &0u8).is_err());
+}
+
+#[test]
+fn journal_reopen() {
+    use binwrite::record::Journal;
+    use std::io::Cursor;
+
+    let options = binwrite::writer_option_new!(endian: binwrite::Endian::Big);
+    let framing = Framing::new(0, 1, None);
+
+    let mut journal = Journal::create(Cursor::new(vec![]), framing, options.clone()).unwrap();
+    journal.append(0, &[1u8, 2]).unwrap();
+    journal.commit().unwrap();
+    let file = journal.into_inner().into_inner();
+    assert_eq!(&file[..3], &[2, 1, 2]);
+    assert_eq!(&file[3..19], &[0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]);
+    assert_eq!(&file[19..], b"BWJL");
+
+    let mut journal = Journal::open(Cursor::new(file), framing, options.clone()).unwrap();
+    assert_eq!(journal.append(0, &3u8).unwrap(), 3);
+    journal.commit().unwrap();
+    assert_eq!(journal.offsets(), &[0, 3]);
+    let file = journal.into_inner().into_inner();
+    assert_eq!(&file[..5], &[2, 1, 2, 1, 3]);
+    assert_eq!(file.len(), 5 + 16 + 12);
+
+    let mut corrupt = file.clone();
+    *corrupt.last_mut().unwrap() = 0;
+    assert!(Journal::open(Cursor::new(corrupt), framing, options.clone()).is_err());
+    assert!(Journal::open(Cursor::new(vec![0; 4]), framing, options.clone()).is_err());
+
+    // Creating over existing data would leave a stale footer at the end
+    assert!(Journal::create(Cursor::new(vec![0; 4]), framing, options).is_err());
+}
@@ -0,0 +1,56 @@
+#![cfg(any(feature = "arbitrary", feature = "proptest"))]
+use binwrite::testing;
+
+#[test]
+fn roundtrip() {
+    let value = (1u16, 2u32);
+    testing::assert_writes_fixed_size(&value, &Default::default());
+    testing::assert_roundtrip(&value, &binwrite::writer_option_new!(endian: binwrite::Endian::Little), |bytes| {
+        Ok::<_, ()>((
+            u16::from_le_bytes([bytes[0], bytes[1]]),
+            u32::from_le_bytes([bytes[2], bytes[3], bytes[4], bytes[5]]),
+        ))
+    });
+}
+
+#[cfg(feature = "arbitrary")]
+#[test]
+fn fuzz_write() {
+    let bytes = testing::fuzz_write::<(u8, u16)>(&[1, 2, 3], &Default::default()).unwrap();
+    assert_eq!(bytes.len(), 3);
+}
+
+#[cfg(feature = "proptest")]
+#[test]
+fn proptest_write() {
+    testing::proptest_write::<(u32, Vec<u8>), _>(&Default::default(), |value, bytes| {
+        assert_eq!(bytes.len(), 4 + value.1.len());
+    });
+}
+
+#[test]
+fn aligned_array_fixed_size() {
+    use binwrite::{BinWrite, FixedSize, WriterOption};
+    use std::io::{Result, Write};
+
+    #[derive(Debug)]
+    struct Record(u8, u16);
+
+    impl BinWrite for Record {
+        const ALIGNMENT: usize = 4;
+
+        fn write_options<W: Write>(&self, writer: &mut W, options: &WriterOption) -> Result<()> {
+            (self.0, self.1).write_options(writer, options)
+        }
+    }
+
+    impl FixedSize for Record {
+        const SIZE: usize = 3;
+    }
+
+    let bytes = testing::assert_writes_fixed_size(
+        &[Record(1, 2), Record(3, 4)],
+        &binwrite::writer_option_new!(endian: binwrite::Endian::Big)
+    );
+    assert_eq!(bytes, vec![1, 0, 2, 0, 3, 0, 4, 0]);
+}
@@ -0,0 +1,134 @@
+use std::io::{Cursor, Seek, SeekFrom, Write};
+use binwrite::write_track::WriteTrack;
+
+#[test]
+fn seek_passthrough() {
+    let mut cursor = Cursor::new(vec![0xFFu8; 2]);
+    cursor.set_position(2);
+
+    let mut writer = WriteTrack::new_seekable(&mut cursor).unwrap();
+    writer.write_all(&[1, 2, 3, 4]).unwrap();
+    assert_eq!(writer.seek(SeekFrom::Start(1)).unwrap(), 1);
+    writer.write_all(&[5]).unwrap();
+    assert_eq!(writer.pos(), 2);
+    assert!(writer.seek(SeekFrom::Current(-3)).is_err());
+    assert_eq!(writer.seek(SeekFrom::End(0)).unwrap(), 4);
+
+    assert_eq!(cursor.into_inner(), vec![0xFF, 0xFF, 1, 5, 3, 4]);
+}
+
+#[test]
+fn no_seek() {
+    let mut writer = WriteTrack::new(vec![]);
+    writer.write_all(&[1, 2]).unwrap();
+    assert_eq!(writer.stream_position().unwrap(), 2);
+    assert!(writer.seek(SeekFrom::Start(0)).is_err());
+}
+
+#[test]
+fn snapshot_restore() {
+    use binwrite::SnapshotBuffer;
+
+    let mut buffer = SnapshotBuffer::new();
+    buffer.write_all(&[1, 2, 3]).unwrap();
+    buffer.snapshot("a");
+
+    // Backfill an earlier byte and append
+    buffer.seek(SeekFrom::Start(1)).unwrap();
+    buffer.write_all(&[9]).unwrap();
+    buffer.seek(SeekFrom::End(0)).unwrap();
+    buffer.snapshot("b");
+    buffer.write_all(&[4, 5]).unwrap();
+
+    let diff = buffer.diff("a").unwrap();
+    assert_eq!((diff.old_len, diff.new_len), (3, 5));
+    assert_eq!(diff.changed, vec![1..2, 3..5]);
+    assert_eq!(buffer.diff("b").unwrap().changed, vec![3..5]);
+
+    buffer.restore("a").unwrap();
+    assert_eq!(buffer.as_bytes(), &[1, 2, 3]);
+    assert_eq!(buffer.pos(), 3);
+    assert!(buffer.restore("b").is_err());
+
+    buffer.write_all(&[6]).unwrap();
+    buffer.release("a").unwrap();
+    assert_eq!(buffer.into_bytes(), vec![1, 2, 3, 6]);
+}
+
+#[test]
+fn fragment_parallel_splice() {
+    use binwrite::{BinWrite, Fragment};
+
+    let options = binwrite::writer_option_new!(endian: binwrite::Endian::Little);
+    let entries: Vec<Fragment> = std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..3u8).map(|i| {
+            let options = &options;
+            scope.spawn(move || {
+                let mut entry = Fragment::new(options);
+                entry.offset_to::<u16, _>("data");
+                entry.offset_to::<u16, _>("end");
+                entry.label("data").unwrap();
+                vec![i; i as usize + 1].write(&mut entry).unwrap();
+                entry
+            })
+        }).collect();
+        handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+    });
+
+    let mut file = Fragment::new(&options);
+    for entry in entries {
+        file.splice(entry);
+    }
+    file.label("end").unwrap();
+
+    assert_eq!(
+        file.finish(0x100).unwrap(),
+        vec![
+            0x04, 0x01, 0x12, 0x01, 0,
+            0x09, 0x01, 0x12, 0x01, 1, 1,
+            0x0F, 0x01, 0x12, 0x01, 2, 2, 2,
+        ]
+    );
+
+    let mut missing = Fragment::new(&options);
+    missing.offset_to::<u8, _>("nowhere");
+    assert!(missing.finish(0).is_err());
+}
+
+#[test]
+fn fragment_relocations() {
+    use binwrite::{BinWrite, Fragment};
+
+    let options = binwrite::writer_option_new!(endian: binwrite::Endian::Big);
+    let mut child = Fragment::new(&options);
+    0u8.write(&mut child).unwrap();
+    child.offset_to::<u16, _>("target");
+    child.label("target").unwrap();
+
+    let mut file = Fragment::new(&options);
+    file.offset_to::<u32, _>("table");
+    file.splice(child);
+    file.label("table").unwrap();
+
+    let relocations = file.relocations(0x20);
+    let mut bytes = file.finish(0x20).unwrap();
+    relocations.iter().map(|&pos| pos as u32).collect::<Vec<_>>().write_options(&mut bytes, &options).unwrap();
+
+    assert_eq!(
+        bytes,
+        vec![
+            0, 0, 0, 0x27, 0, 0, 0x27,
+            0, 0, 0, 0x20, 0, 0, 0, 0x25,
+        ]
+    );
+}
+
+#[test]
+fn flash_image_offset_overflow() {
+    use binwrite::FlashImage;
+
+    let mut image = FlashImage::new(0x10);
+    let options = binwrite::writer_option_new!(endian: binwrite::Endian::Big);
+    let err = image.place_at(usize::MAX - 1, &0u32, &options).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+}
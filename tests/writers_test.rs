@@ -0,0 +1,1199 @@
+use binwrite::writers::LenPrefix;
+
+#[test]
+fn len_prefix() {
+    let mut bytes = vec![];
+    LenPrefix::<u16>::write(&vec![1u8, 2, 3], &mut bytes, &binwrite::writer_option_new!(endian: binwrite::Endian::Big)).unwrap();
+    assert_eq!(bytes, vec![0, 3, 1, 2, 3]);
+}
+
+#[test]
+fn nested_len_prefix() {
+    let jagged: Vec<Vec<u8>> = vec![vec![1], vec![], vec![2, 3]];
+    let mut bytes = vec![];
+    LenPrefix::<u32, LenPrefix<u8>>::write(&jagged, &mut bytes, &binwrite::writer_option_new!(endian: binwrite::Endian::Little)).unwrap();
+    assert_eq!(bytes, vec![3, 0, 0, 0, 1, 1, 0, 2, 2, 3]);
+}
+
+#[test]
+fn len_prefix_overflow() {
+    let mut bytes = vec![];
+    assert!(LenPrefix::<u8>::write(&vec![0u8; 0x100], &mut bytes, &Default::default()).is_err());
+}
+
+#[test]
+fn str_len_prefix() {
+    use binwrite::writers::{Chars, StrLenPrefix, Utf16Units};
+
+    let options = binwrite::writer_option_new!(endian: binwrite::Endian::Big);
+    let mut bytes = vec![];
+    StrLenPrefix::<u8>::write("h\u{e9}", &mut bytes, &options).unwrap();
+    assert_eq!(bytes, vec![3, b'h', 0xC3, 0xA9]);
+
+    let mut bytes = vec![];
+    StrLenPrefix::<u8, Chars>::write("\u{1F600}", &mut bytes, &options).unwrap();
+    assert_eq!(bytes, vec![1, 0xF0, 0x9F, 0x98, 0x80]);
+
+    let mut bytes = vec![];
+    StrLenPrefix::<u16, Utf16Units>::write_utf16("\u{1F600}", &mut bytes, &options).unwrap();
+    assert_eq!(bytes, vec![0, 2, 0xD8, 0x3D, 0xDE, 0x00]);
+}
+
+#[test]
+fn assert_size() {
+    use binwrite::{BinWrite, writers::SizeChecked};
+
+    let mut bytes = vec![];
+    SizeChecked::new(&(1u32, 2u16), 6).write(&mut bytes).unwrap();
+    assert_eq!(bytes.len(), 6);
+    assert!(SizeChecked::new(&(1u32, 2u16), 8).write(&mut vec![]).is_err());
+    assert!((binwrite::writers::assert_size(4))(&1u32).write(&mut vec![]).is_ok());
+}
+
+#[test]
+fn assert_offset() {
+    use binwrite::{BinWrite, write_track::WriteTrack, writers::assert_offset};
+
+    let mut writer = WriteTrack::new(vec![]);
+    0x10u64.write(&mut writer).unwrap();
+    assert_offset(&mut writer, 8).unwrap();
+    assert!(assert_offset(&mut writer, 0x10).is_err());
+}
+
+#[test]
+fn aligned_records() {
+    use binwrite::{BinWrite, writers::Aligned};
+
+    let records = [(1u8, 2u16), (3u8, 4u16)];
+    let aligned: Vec<_> = records.iter().map(|record| Aligned::new(record, 4)).collect();
+    let mut bytes = vec![];
+    aligned.write_options(&mut bytes, &binwrite::writer_option_new!(endian: binwrite::Endian::Big)).unwrap();
+    assert_eq!(bytes, vec![1, 0, 2, 0, 3, 0, 4, 0]);
+}
+
+#[test]
+fn dynamic_align() {
+    use binwrite::{BinWrite, write_track::WriteTrack, writers};
+
+    let block_size = 8u32;
+    let mut writer = WriteTrack::new(vec![]);
+    block_size.write(&mut writer).unwrap();
+    writers::align(&mut writer, block_size as usize).unwrap();
+    writers::pad(&mut writer, 2).unwrap();
+    assert_eq!(writer.pos(), 10);
+    assert!(writers::align(&mut writer, 0).is_err());
+}
+
+#[test]
+fn repeat() {
+    use binwrite::BinWrite;
+
+    let mut bytes = vec![];
+    (binwrite::writers::repeat(3))(&0xAA55u16).write_options(&mut bytes, &binwrite::writer_option_new!(endian: binwrite::Endian::Big)).unwrap();
+    assert_eq!(bytes, vec![0xAA, 0x55, 0xAA, 0x55, 0xAA, 0x55]);
+}
+
+#[test]
+fn terminated() {
+    use binwrite::{BinWrite, writers};
+
+    let entries = vec![(1u8, 2u8), (3, 4)];
+    let mut bytes = vec![];
+    (writers::terminated(0xFFu8))(&entries).write(&mut bytes).unwrap();
+    assert_eq!(bytes, vec![1, 2, 3, 4, 0xFF]);
+
+    let mut bytes = vec![];
+    (writers::terminated_with(|_: &Vec<(u8, u8)>| (0u8, 0u8)))(&entries).write(&mut bytes).unwrap();
+    assert_eq!(bytes, vec![1, 2, 3, 4, 0, 0]);
+}
+
+#[test]
+fn reversed() {
+    let mut bytes = vec![];
+    binwrite::writers::reversed(&vec![1u16, 2, 3], &mut bytes, &binwrite::writer_option_new!(endian: binwrite::Endian::Little)).unwrap();
+    assert_eq!(bytes, vec![3, 0, 2, 0, 1, 0]);
+
+    let swapped = (binwrite::writers::swap_bytes(2))(vec![1, 2, 3, 4, 5]);
+    assert_eq!(swapped, vec![2, 1, 4, 3, 5]);
+}
+
+#[test]
+fn middle_endian() {
+    use binwrite::{Endian, writer_option_new, writers};
+
+    let mut bytes = vec![];
+    writers::pdp_endian(&0x0A0B_0C0Du32, &mut bytes, &writer_option_new!(endian: Endian::Big)).unwrap();
+    assert_eq!(bytes, vec![0x0B, 0x0A, 0x0D, 0x0C]);
+
+    let mut bytes = vec![];
+    writers::word_swapped(&0x0102_0304_0506_0708u64, &mut bytes, &writer_option_new!(endian: Endian::Big)).unwrap();
+    assert_eq!(bytes, vec![5, 6, 7, 8, 1, 2, 3, 4]);
+
+    let mut bytes = vec![];
+    writers::pdp_endian(&0x0102_0304_0506_0708u64, &mut bytes, &writer_option_new!(endian: Endian::Big)).unwrap();
+    assert_eq!(bytes, vec![2, 1, 4, 3, 6, 5, 8, 7]);
+
+    // Each element is swapped separately
+    let mut bytes = vec![];
+    writers::word_swapped(&[0x0102_0304u32, 0x0506_0708], &mut bytes, &writer_option_new!(endian: Endian::Big)).unwrap();
+    assert_eq!(bytes, vec![3, 4, 1, 2, 7, 8, 5, 6]);
+
+    let mut bytes = vec![];
+    writers::pdp_endian(&[0x0102_0304u32, 0x0506_0708], &mut bytes, &writer_option_new!(endian: Endian::Big)).unwrap();
+    assert_eq!(bytes, vec![2, 1, 4, 3, 6, 5, 8, 7]);
+}
+
+#[test]
+fn char_encodings() {
+    use binwrite::{BinWrite, Endian, writer_option_new, writers};
+
+    let mut bytes = vec![];
+    'é'.write(&mut bytes).unwrap();
+    assert_eq!(bytes, "é".as_bytes());
+
+    let options = writer_option_new!(endian: Endian::Big);
+    let mut bytes = vec![];
+    writers::utf16_char(&'😀', &mut bytes, &options).unwrap();
+    assert_eq!(bytes, vec![0xD8, 0x3D, 0xDE, 0x00]);
+
+    let mut bytes = vec![];
+    writers::u32_char(&'A', &mut bytes, &options).unwrap();
+    assert_eq!(bytes, vec![0, 0, 0, 0x41]);
+}
+
+#[test]
+fn layout_checked() {
+    use binwrite::BinWrite;
+
+    let mut bytes = vec![];
+    binwrite::writers::check_layout(&(1u32, [0u8; 3])).write(&mut bytes).unwrap();
+    assert_eq!(bytes.len(), 7);
+}
+
+#[test]
+fn option_presence() {
+    use binwrite::{Endian, writer_option_new, writers};
+
+    let options = writer_option_new!(endian: Endian::Big);
+    let mut bytes = vec![];
+    writers::option_byte(&Some(2u16), &mut bytes, &options).unwrap();
+    writers::option_byte(&None::<u16>, &mut bytes, &options).unwrap();
+    writers::option_u32(&Some(3u8), &mut bytes, &options).unwrap();
+    assert_eq!(bytes, vec![1, 0, 2, 0, 0, 0, 0, 1, 3]);
+}
+
+#[test]
+fn bitset() {
+    use binwrite::{BinWrite, writers::{bitset, BitOrder}};
+
+    let flags = vec![true, false, false, false, false, false, false, true, true];
+    let mut bytes = vec![];
+    (bitset(BitOrder::Msb0, 1))(&flags).write(&mut bytes).unwrap();
+    assert_eq!(bytes, vec![0x81, 0x80]);
+
+    let mut bytes = vec![];
+    (bitset(BitOrder::Lsb0, 4))(&flags).write(&mut bytes).unwrap();
+    assert_eq!(bytes, vec![0x81, 0x01, 0, 0]);
+}
+
+#[test]
+fn nibbles() {
+    use binwrite::{BinWrite, writers::{nibbles, NibbleOrder}};
+
+    let indices = vec![1u8, 2, 3];
+    let mut bytes = vec![];
+    (nibbles(NibbleOrder::HighFirst))(&indices).write(&mut bytes).unwrap();
+    assert_eq!(bytes, vec![0x12, 0x30]);
+
+    let mut bytes = vec![];
+    (nibbles(NibbleOrder::LowFirst))(&indices).write(&mut bytes).unwrap();
+    assert_eq!(bytes, vec![0x21, 0x03]);
+
+    assert!((nibbles(NibbleOrder::LowFirst))(&vec![0x10u8]).write(&mut vec![]).is_err());
+}
+
+#[test]
+fn delta() {
+    let timestamps = vec![100u16, 105, 103, 110];
+    assert_eq!(binwrite::writers::delta(&timestamps), vec![100, 5, 0xFFFE, 7]);
+}
+
+#[test]
+fn rle() {
+    use binwrite::{BinWrite, writers::{self, Rle}};
+
+    // Example from Apple's PackBits technical note
+    let data = vec![
+        0xAA, 0xAA, 0xAA, 0x80, 0x00, 0x2A, 0xAA, 0xAA, 0xAA, 0xAA, 0x80, 0x00, 0x2A, 0x22, 0xAA,
+        0xAA, 0xAA, 0xAA, 0xAA, 0xAA, 0xAA, 0xAA, 0xAA, 0xAA,
+    ];
+    let mut bytes = vec![];
+    writers::packbits(data).write(&mut bytes).unwrap();
+    assert_eq!(bytes, vec![
+        0xFE, 0xAA, 0x02, 0x80, 0x00, 0x2A, 0xFD, 0xAA, 0x03, 0x80, 0x00, 0x2A, 0x22, 0xF7, 0xAA,
+    ]);
+
+    let pixels = vec![1, 2, 1, 2, 1, 2, 3, 4];
+    assert_eq!(Rle::tga(2).encode(&pixels).unwrap(), vec![0x82, 1, 2, 0x00, 3, 4]);
+    assert!(Rle::tga(3).encode(&pixels).is_err());
+
+    // Zero limits would never make progress, and limits past the header range would corrupt it
+    assert!(Rle { min_run: 0, max_run: 0, ..Rle::PACKBITS }.encode(&pixels).is_err());
+    assert!(Rle { max_literal: 0, ..Rle::PACKBITS }.encode(&pixels).is_err());
+    assert!(Rle { max_literal: 200, ..Rle::PACKBITS }.encode(&[0, 1].repeat(100)).is_err());
+    assert!(Rle { max_run: 200, ..Rle::PACKBITS }.encode(&[0; 200]).is_err());
+}
+
+#[test]
+fn len_of() {
+    use binwrite::{BinWrite, writers};
+
+    let names = vec!["a".to_string(), "bc".to_string()];
+    let mut bytes = vec![];
+    names.write(&mut bytes).unwrap();
+    writers::count_of::<u8, _, _>(&names).unwrap().write(&mut bytes).unwrap();
+    writers::byte_len_of::<u8, _>(&names, &Default::default()).unwrap().write(&mut bytes).unwrap();
+    assert_eq!(bytes, b"abc\x02\x03");
+    assert!(writers::count_of::<u8, _, _>(&vec![0u8; 0x100]).is_err());
+}
+
+#[test]
+fn bps() {
+    use binwrite::writers::bps::{write_varint, BpsPatch};
+
+    let mut bytes = vec![];
+    for value in &[0, 0x7F, 0x80, 0x407F] {
+        write_varint(*value, &mut bytes).unwrap();
+    }
+    assert_eq!(bytes, vec![0x80, 0xFF, 0x00, 0x80, 0x7F, 0xFF]);
+
+    assert_eq!(BpsPatch::from_diff(b"", b"123456789").target_crc, 0xCBF4_3926);
+}
+
+#[test]
+fn ips_eof_offset() {
+    use binwrite::writers::ips::{IpsPatch, IpsRecord};
+
+    let original = vec![0u8; 0x454F48];
+    let mut modified = original.clone();
+    modified[0x454F46] = 1;
+    let patch = IpsPatch::from_diff(&original, &modified);
+    assert_eq!(patch.records, vec![IpsRecord::Data { offset: 0x454F45, data: vec![0, 1] }]);
+}
+
+#[test]
+fn large_padding() {
+    use std::io::Cursor;
+    use binwrite::writers;
+
+    let mut bytes = vec![];
+    writers::pad(&mut bytes, 0x2345).unwrap();
+    assert_eq!(bytes, vec![0; 0x2345]);
+
+    let mut cursor = Cursor::new(vec![1u8]);
+    cursor.set_position(1);
+    writers::pad_sparse(&mut cursor, 0x10_0000 - 1).unwrap();
+    assert_eq!(cursor.get_ref().len(), 0x10_0000);
+    assert!(cursor.get_ref()[1..].iter().all(|byte| *byte == 0));
+}
+
+#[test]
+fn ascii_numbers() {
+    use binwrite::{BinWrite, writers::{ascii, ascii_decimal, ascii_hex, Radix}};
+
+    let mut bytes = vec![];
+    (ascii_hex(8))(&0xBEEFu32).write(&mut bytes).unwrap();
+    (ascii_decimal(3))(&7u8).write(&mut bytes).unwrap();
+    (ascii(Radix::LowerHex, 4, b' '))(&0xABu16).write(&mut bytes).unwrap();
+    assert_eq!(bytes, b"0000BEEF007  ab");
+
+    let mut bytes = vec![];
+    (ascii_decimal(4))(&-5i32).write(&mut bytes).unwrap();
+    (ascii(Radix::Decimal, 4, b' '))(&-5i32).write(&mut bytes).unwrap();
+    assert_eq!(bytes, b"-005  -5");
+
+    assert!((ascii_hex(2))(&0x100u32).write(&mut vec![]).is_err());
+}
+
+#[test]
+fn ascii_floats() {
+    use binwrite::{BinWrite, writers::{ascii_float, FloatFormat}};
+
+    let mut bytes = vec![];
+    (ascii_float(FloatFormat::Fixed(6)))(&1.5f32).write(&mut bytes).unwrap();
+    bytes.push(b'|');
+    (ascii_float(FloatFormat::Scientific(6)))(&vec![1.5f64, -0.00012, 1e100]).write(&mut bytes).unwrap();
+    bytes.push(b'|');
+    (ascii_float(FloatFormat::Fixed(2)))(&[f64::NAN, f64::NEG_INFINITY][..]).write(&mut bytes).unwrap();
+    assert_eq!(
+        String::from_utf8(bytes).unwrap(),
+        "1.500000|1.500000e+00 -1.200000e-04 1.000000e+100|nan -inf"
+    );
+}
+
+#[test]
+fn intel_hex_extended_address() {
+    use binwrite::writers::intel_hex::{IntelHex, IntelHexRecord};
+
+    let records = IntelHex::from_bytes(0xFFFE, &[1, 2, 3, 4], 16).records;
+    assert_eq!(records, vec![
+        IntelHexRecord::Data { address: 0xFFFE, data: vec![1, 2] },
+        IntelHexRecord::ExtendedLinearAddress(1),
+        IntelHexRecord::Data { address: 0, data: vec![3, 4] },
+        IntelHexRecord::EndOfFile,
+    ]);
+}
+
+#[test]
+fn dns_names() {
+    use binwrite::writers::{dns_name, DnsNameCompressor};
+
+    let mut bytes = vec![];
+    dns_name("example.com.", &mut bytes, &Default::default()).unwrap();
+    dns_name("", &mut bytes, &Default::default()).unwrap();
+    assert_eq!(bytes, b"\x07example\x03com\x00\x00");
+
+    let mut compressor = DnsNameCompressor::new();
+    let mut bytes = vec![0; 12];
+    compressor.write("www.example.com", bytes.len(), &mut bytes).unwrap();
+    compressor.write("mail.Example.com", bytes.len(), &mut bytes).unwrap();
+    assert_eq!(&bytes[12..], b"\x03www\x07example\x03com\x00\x04mail\xC0\x10");
+}
+
+#[test]
+fn masked_payload() {
+    use binwrite::{writer_option_new, writers};
+
+    let options = writer_option_new!(mask: Some([0x37, 0xFA, 0x21, 0x3D]));
+    let mut bytes = vec![];
+    writers::mask_key(&(), &mut bytes, &options).unwrap();
+    writers::masked("Hello", &mut bytes, &options).unwrap();
+    // Example from RFC 6455 section 5.7
+    assert_eq!(bytes, vec![0x37, 0xFA, 0x21, 0x3D, 0x7F, 0x9F, 0x4D, 0x51, 0x58]);
+
+    let mut bytes = vec![];
+    writers::masked("Hi", &mut bytes, &Default::default()).unwrap();
+    assert_eq!(bytes, b"Hi");
+}
+
+#[cfg(feature = "encoding_rs")]
+#[test]
+fn fixed_encoded_shift_jis() {
+    use binwrite::BinWrite;
+    use binwrite::writers::{FixedEncoded, Overflow};
+    use encoding_rs::SHIFT_JIS;
+
+    let mut bytes = vec![];
+    FixedEncoded::new("aあい", SHIFT_JIS, 4, Overflow::Truncate, 0).write(&mut bytes).unwrap();
+    assert_eq!(bytes, vec![b'a', 0x82, 0xA0, 0]);
+
+    let mut bytes = vec![];
+    FixedEncoded::new("aあ", SHIFT_JIS, 6, Overflow::Error, 0xFF).write(&mut bytes).unwrap();
+    assert_eq!(bytes, vec![b'a', 0x82, 0xA0, 0xFF, 0xFF, 0xFF]);
+
+    assert!(FixedEncoded::new("aあい", SHIFT_JIS, 4, Overflow::Error, 0).write(&mut vec![]).is_err());
+    assert!(FixedEncoded::new("\u{1F600}", SHIFT_JIS, 4, Overflow::Truncate, 0).write(&mut vec![]).is_err());
+
+    // ISO-2022-JP has to switch back to ASCII at the end (ESC ( B), which counts toward the size
+    let bytes = FixedEncoded::new("あい", encoding_rs::ISO_2022_JP, 8, Overflow::Truncate, 0).encode().unwrap();
+    assert_eq!(bytes, b"\x1b$B$\x22\x1b(B");
+    assert!(FixedEncoded::new("あい", encoding_rs::ISO_2022_JP, 8, Overflow::Error, 0).encode().is_err());
+}
+
+#[cfg(feature = "encoding_rs")]
+#[test]
+fn streamed_encoded_shift_jis() {
+    use binwrite::BinWrite;
+    use binwrite::writers::Encoded;
+    use encoding_rs::SHIFT_JIS;
+
+    // Long enough to fill the stack buffer several times
+    let string = "aあ".repeat(1000);
+    let mut bytes = vec![];
+    Encoded::new(&string, SHIFT_JIS).write(&mut bytes).unwrap();
+    assert_eq!(bytes, [b'a', 0x82, 0xA0].repeat(1000));
+
+    assert!(Encoded::new("a\u{1F600}", SHIFT_JIS).write(&mut vec![]).is_err());
+}
+
+#[test]
+fn chunked_utf16_string() {
+    use binwrite::{writers, writer_option_new, Endian};
+
+    let string = "aあ😀".repeat(300);
+    let mut bytes = vec![];
+    writers::utf16_string(&string, &mut bytes, &writer_option_new!(endian: Endian::Big)).unwrap();
+    let expected: Vec<u8> = string.encode_utf16().flat_map(|unit| unit.to_be_bytes()).collect();
+    assert_eq!(bytes, expected);
+
+    let mut bytes = vec![];
+    writers::null_terminated_string(&string, &mut bytes, &Default::default()).unwrap();
+    assert_eq!(&bytes[..bytes.len() - 1], string.as_bytes());
+    assert_eq!(bytes.last(), Some(&0));
+}
+
+#[test]
+fn byte_order_marked() {
+    use binwrite::{BinWrite, Endian};
+    use binwrite::writers::ByteOrderMarked;
+
+    let mut bytes = vec![];
+    ByteOrderMarked::new(&(1u16, 2u32), Endian::Little).write(&mut bytes).unwrap();
+    assert_eq!(bytes, vec![0xFF, 0xFE, 1, 0, 2, 0, 0, 0]);
+
+    let mut bytes = vec![];
+    ByteOrderMarked::with_marker(&1u16, Endian::Big, 0x4259).write(&mut bytes).unwrap();
+    assert_eq!(bytes, b"BY\0\x01");
+}
+
+#[test]
+fn sparse_table() {
+    use binwrite::BinWrite;
+    use binwrite::writers::SparseTable;
+    use std::collections::BTreeMap;
+
+    let mut entries = BTreeMap::new();
+    entries.insert(1usize, 5u8);
+    entries.insert(3, 7);
+
+    let mut bytes = vec![];
+    SparseTable::new(&entries, 5, 0xFF).write(&mut bytes).unwrap();
+    assert_eq!(bytes, vec![0xFF, 5, 0xFF, 7, 0xFF]);
+
+    assert!(SparseTable::new(&entries, 3, 0xFF).write(&mut vec![]).is_err());
+}
+
+#[test]
+fn offset_table() {
+    use binwrite::writers::OffsetTable;
+
+    let options = binwrite::writer_option_new!(endian: binwrite::Endian::Little);
+    let items = vec![vec![1u8, 2], vec![], vec![3]];
+
+    let mut bytes = vec![];
+    OffsetTable::<u16>::write(&items, &mut bytes, &options).unwrap();
+    assert_eq!(bytes, vec![6, 0, 8, 0, 8, 0, 1, 2, 3]);
+
+    let mut bytes = vec![];
+    OffsetTable::<u8>::write_counted(&items, &mut bytes, &options).unwrap();
+    assert_eq!(bytes, vec![3, 4, 6, 6, 1, 2, 3]);
+}
+
+#[test]
+fn blob_pool_alignment() {
+    use binwrite::writers::BlobPool;
+
+    let options = binwrite::writer_option_new!(endian: binwrite::Endian::Big);
+    let mut pool = BlobPool::<str>::with_alignment(4);
+    assert_eq!(pool.insert("abc", &options).unwrap(), 0);
+    assert_eq!(pool.insert("de", &options).unwrap(), 4);
+    assert_eq!(pool.insert("abc", &options).unwrap(), 0);
+    assert_eq!(pool.as_bytes(), b"abc\0de");
+}
+
+#[test]
+fn crc_catalogue_check_values() {
+    use binwrite::checksum::Crc;
+
+    let check = b"123456789";
+    assert_eq!(Crc::CRC8.checksum(check), 0xF4);
+    assert_eq!(Crc::CRC16_ARC.checksum(check), 0xBB3D);
+    assert_eq!(Crc::CRC16_IBM_3740.checksum(check), 0x29B1);
+    assert_eq!(Crc::CRC16_XMODEM.checksum(check), 0x31C3);
+    assert_eq!(Crc::CRC32.checksum(check), 0xCBF43926);
+    assert_eq!(Crc::CRC32_BZIP2.checksum(check), 0xFC891918);
+    assert_eq!(Crc::CRC32C.checksum(check), 0xE3069283);
+    assert_eq!(Crc::CRC64_XZ.checksum(check), 0x995DC9BBDF1939FA);
+    // CRC-5/USB, a width that isn't a whole number of bytes
+    assert_eq!(Crc::new(5, 0x05, 0x1F, true, true, 0x1F).checksum(check), 0x19);
+
+    assert!(std::panic::catch_unwind(|| Crc::new(0, 0x07, 0, false, false, 0)).is_err());
+    assert!(std::panic::catch_unwind(|| Crc::new(65, 0x07, 0, false, false, 0)).is_err());
+    // Widths set directly on the field are clamped rather than overflowing
+    assert_eq!(Crc { width: 0, ..Crc::CRC8 }.byte_len(), 1);
+    assert_eq!(Crc { width: 0, ..Crc::CRC8 }.checksum(check), Crc { width: 1, ..Crc::CRC8 }.checksum(check));
+}
+
+#[test]
+fn record_encryption() {
+    use binwrite::BinWrite;
+    use binwrite::writers::{Encrypted, RecordEncryptor};
+
+    let options = binwrite::writer_option_new!(key: Some(vec![1, 2]));
+    let mut bytes = vec![];
+    let mut encryptor = RecordEncryptor::new(0);
+    encryptor.write(&[0x10u8, 0x20], &mut bytes, &options).unwrap();
+    encryptor.write(&0x10u8, &mut bytes, &options).unwrap();
+    assert_eq!(bytes, vec![0x11, 0x33, 0x22]);
+    assert_eq!(encryptor.iv(), 0x22);
+
+    let mut bytes = vec![];
+    Encrypted::new(&[0x10u8, 0x20], 0).write_options(&mut bytes, &options).unwrap();
+    assert_eq!(bytes, vec![0x11, 0x33]);
+
+    assert!(Encrypted::new(&0u8, 0).write(&mut vec![]).is_err());
+}
+
+#[test]
+fn scramble() {
+    use binwrite::writers::{add_constant, rotate_bits_left, rotate_bits_right, swap_nibbles, xor_constant};
+
+    assert_eq!(swap_nibbles(vec![0x12, 0xAB]), vec![0x21, 0xBA]);
+    assert_eq!(rotate_bits_left(1)(vec![0x81]), vec![0x03]);
+    assert_eq!(rotate_bits_right(1)(vec![0x81]), vec![0xC0]);
+    assert_eq!(add_constant(0x80)(vec![0x7F, 0x90]), vec![0xFF, 0x10]);
+    assert_eq!(xor_constant(0xFF)(vec![0x0F]), vec![0xF0]);
+}
+
+#[test]
+fn only_if_endian() {
+    use binwrite::{BinWrite, Endian};
+    use binwrite::writers::OnlyIfEndian;
+
+    let mut bytes = vec![];
+    for endian in [Endian::Big, Endian::Little] {
+        (1u8, OnlyIfEndian::new(&0u16, Endian::Big), 2u8)
+            .write_options(&mut bytes, &binwrite::writer_option_new!(endian: endian))
+            .unwrap();
+    }
+    assert_eq!(bytes, vec![1, 0, 0, 2, 1, 2]);
+
+    let native = if cfg!(target_endian = "little") { Endian::Little } else { Endian::Big };
+    assert_eq!(Endian::Native.resolve(), native);
+}
+
+#[test]
+fn zip_stored() {
+    use binwrite::BinWrite;
+    use binwrite::writers::zip::ZipArchive;
+
+    let mut archive = ZipArchive::new();
+    archive.add_stored("a.txt", b"abc".to_vec());
+
+    let mut bytes = vec![];
+    archive.write(&mut bytes).unwrap();
+    assert_eq!(bytes.len(), (30 + 5 + 3) + (46 + 5) + 22);
+    // CRC-32 of "abc"
+    assert_eq!(&bytes[14..18], &0x352441C2u32.to_le_bytes());
+    assert_eq!(&bytes[30..38], b"a.txtabc");
+
+    let directory = &bytes[38..];
+    assert_eq!(&directory[..4], b"PK\x01\x02");
+    let end = &bytes[bytes.len() - 22..];
+    assert_eq!(&end[10..12], &[1, 0]);
+    assert_eq!(&end[12..16], &51u32.to_le_bytes());
+    assert_eq!(&end[16..20], &38u32.to_le_bytes());
+}
+
+#[test]
+fn tar_header() {
+    use binwrite::writers::tar::TarHeader;
+
+    let block = TarHeader::file("a.txt", 10).to_block().unwrap();
+    assert_eq!(&block[..5], b"a.txt");
+    assert_eq!(&block[124..136], b"00000000012\0");
+
+    let checksum: u32 = block.iter().enumerate()
+        .map(|(i, byte)| if (148..156).contains(&i) { b' ' as u32 } else { *byte as u32 })
+        .sum();
+    assert_eq!(&block[148..156], format!("{:06o}\0 ", checksum).as_bytes());
+
+    let long_path = format!("{}/{}", "d".repeat(120), "f".repeat(90));
+    let block = TarHeader::file(long_path, 0).to_block().unwrap();
+    assert_eq!(&block[..90], "f".repeat(90).as_bytes());
+    assert_eq!(&block[345..465], "d".repeat(120).as_bytes());
+
+    assert!(TarHeader::file("x".repeat(101), 0).to_block().is_err());
+}
+
+#[test]
+fn fixed_timestamps() {
+    use binwrite::BinWrite;
+    use binwrite::writers::tar::TarHeader;
+    use binwrite::writers::zip::{dos_datetime, LocalFileHeader, ZipEntry, Method};
+
+    assert_eq!(dos_datetime(0), (0, 0x21));
+    assert_eq!(dos_datetime(946_684_800), (0, 10273));
+    assert_eq!(dos_datetime(1_709_210_096), (25692, 22621));
+
+    let options = binwrite::writer_option_new!(timestamp: Some(946_684_800));
+    let entry = ZipEntry {
+        name: String::from("a"),
+        method: Method::Stored,
+        data: vec![],
+        crc32: 0,
+        uncompressed_size: 0,
+        modified: (1, 1),
+    };
+    let mut bytes = vec![];
+    LocalFileHeader(&entry).write_options(&mut bytes, &options).unwrap();
+    assert_eq!(&bytes[10..14], &[0, 0, 0x21, 0x28]);
+
+    let mut bytes = vec![];
+    let mut header = TarHeader::file("a", 0);
+    header.mtime = 12345;
+    header.write_options(&mut bytes, &options).unwrap();
+    assert_eq!(&bytes[136..148], b"07033241600\0");
+}
+
+#[test]
+fn odd_chunk_padding() {
+    use binwrite::BinWrite;
+    use binwrite::writers::Chunk;
+
+    let mut bytes = vec![];
+    Chunk::new(b"name", "abc")
+        .write_options(&mut bytes, &binwrite::writer_option_new!(endian: binwrite::Endian::Big))
+        .unwrap();
+    assert_eq!(bytes, b"name\0\0\0\x03abc\0");
+}
+
+#[test]
+fn tga_rle_rows() {
+    use binwrite::BinWrite;
+    use binwrite::writers::bitmap::Tga;
+
+    let pixels = [[1, 2, 3, 4]; 6];
+    let mut bytes = vec![];
+    Tga { width: 3, height: 2, pixels: &pixels, rle: true }.write(&mut bytes).unwrap();
+    assert_eq!(bytes[2], 10);
+    // One run packet per row, as packets may not cross rows
+    assert_eq!(&bytes[18..], &[0x82, 3, 2, 1, 4, 0x82, 3, 2, 1, 4]);
+
+    assert!(Tga::new(4, 2, &pixels).write(&mut vec![]).is_err());
+}
+
+#[test]
+fn pixel_formats() {
+    use binwrite::BinWrite;
+    use binwrite::writers::{Converted, PixelFormat};
+
+    let options = binwrite::writer_option_new!(endian: binwrite::Endian::Big);
+    let write = |format, colors: &Vec<u32>| {
+        let mut bytes = vec![];
+        Converted::new(colors, format).write_options(&mut bytes, &options).unwrap();
+        bytes
+    };
+
+    let colors = vec![0xFF0000FFu32, 0x00FF0080];
+    assert_eq!(write(PixelFormat::Rgb565, &colors), vec![0xF8, 0x00, 0x07, 0xE0]);
+    assert_eq!(write(PixelFormat::Rgba4444, &colors), vec![0xF0, 0x0F, 0x0F, 0x08]);
+    assert_eq!(write(PixelFormat::Rgba5551, &colors), vec![0xF8, 0x01, 0x07, 0xC1]);
+    assert_eq!(write(PixelFormat::Bgr888, &colors), vec![0, 0, 0xFF, 0, 0xFF, 0]);
+
+    let mut bytes = vec![];
+    Converted::new(&[1u8, 2, 3, 4], PixelFormat::Argb8888).write(&mut bytes).unwrap();
+    assert_eq!(bytes, vec![4, 1, 2, 3]);
+}
+
+#[test]
+fn pcm_conversion() {
+    use binwrite::{BinWrite, Endian};
+    use binwrite::writers::{Dither, Pcm, PcmFormat};
+
+    let samples = vec![0.0f32, 1.0, -1.0, 2.0];
+    let write = |format, dither, endian| {
+        let mut bytes = vec![];
+        Pcm::new(&samples, format, dither)
+            .write_options(&mut bytes, &binwrite::writer_option_new!(endian: endian))
+            .unwrap();
+        bytes
+    };
+
+    assert_eq!(write(PcmFormat::U8, Dither::Off, Endian::Little), vec![128, 255, 1, 255]);
+    assert_eq!(write(PcmFormat::I16, Dither::Off, Endian::Big), vec![0, 0, 0x7F, 0xFF, 0x80, 0x01, 0x7F, 0xFF]);
+    assert_eq!(&write(PcmFormat::I24, Dither::Off, Endian::Little)[..6], &[0, 0, 0, 0xFF, 0xFF, 0x7F]);
+
+    // Dither adds at most one least significant bit of noise, never overflows and is reproducible
+    let dithered = write(PcmFormat::I16, Dither::Triangular(1), Endian::Little);
+    assert_eq!(dithered, write(PcmFormat::I16, Dither::Triangular(1), Endian::Little));
+    let dithered: Vec<i16> = dithered.chunks(2).map(|sample| i16::from_le_bytes([sample[0], sample[1]])).collect();
+    assert!(dithered[0].abs() <= 1);
+    assert!(dithered[1] >= 32766);
+}
+
+#[cfg(feature = "yaz0")]
+mod yaz0 {
+    use binwrite::writers::LzFormat;
+
+    fn copy_match(out: &mut Vec<u8>, dist: usize, len: usize) {
+        for _ in 0..len {
+            out.push(out[out.len() - dist]);
+        }
+    }
+
+    fn decompress_yaz0(data: &[u8]) -> Vec<u8> {
+        let size = u32::from_be_bytes([data[4], data[5], data[6], data[7]]) as usize;
+        let (mut out, mut pos) = (vec![], 16);
+        while out.len() < size {
+            let flags = data[pos];
+            pos += 1;
+            for bit in (0..8).rev() {
+                if out.len() >= size {
+                    break;
+                }
+                if flags & (1 << bit) != 0 {
+                    out.push(data[pos]);
+                    pos += 1;
+                } else {
+                    let (b1, b2) = (data[pos] as usize, data[pos + 1] as usize);
+                    pos += 2;
+                    let dist = ((b1 & 0xF) << 8 | b2) + 1;
+                    let len = if b1 >> 4 == 0 {
+                        pos += 1;
+                        data[pos - 1] as usize + 0x12
+                    } else {
+                        (b1 >> 4) + 2
+                    };
+                    copy_match(&mut out, dist, len);
+                }
+            }
+        }
+        out
+    }
+
+    fn decompress_yay0(data: &[u8]) -> Vec<u8> {
+        let word = |pos: usize| u32::from_be_bytes([data[pos], data[pos + 1], data[pos + 2], data[pos + 3]]) as usize;
+        let (size, mut links, mut chunks) = (word(4), word(8), word(12));
+        let (mut out, mut masks) = (vec![], 16);
+        while out.len() < size {
+            let mask = word(masks);
+            masks += 4;
+            for bit in (0..32).rev() {
+                if out.len() >= size {
+                    break;
+                }
+                if mask & (1 << bit) != 0 {
+                    out.push(data[chunks]);
+                    chunks += 1;
+                } else {
+                    let link = (data[links] as usize) << 8 | data[links + 1] as usize;
+                    links += 2;
+                    let len = if link >> 12 == 0 {
+                        chunks += 1;
+                        data[chunks - 1] as usize + 0x12
+                    } else {
+                        (link >> 12) + 2
+                    };
+                    copy_match(&mut out, (link & 0xFFF) + 1, len);
+                }
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn roundtrip() {
+        let mut data = b"binwrite binwrite binwrite".to_vec();
+        data.extend_from_slice(&[0xAB; 0x300]);
+        data.extend((0..0x2000u32).map(|i| (i * 7 % 251) as u8));
+
+        let yaz0 = LzFormat::Yaz0.compress(&data).unwrap();
+        assert_eq!(&yaz0[..4], b"Yaz0");
+        assert!(yaz0.len() < data.len());
+        assert_eq!(decompress_yaz0(&yaz0), data);
+
+        let yay0 = LzFormat::Yay0.compress(&data).unwrap();
+        assert_eq!(&yay0[..4], b"Yay0");
+        assert_eq!(decompress_yay0(&yay0), data);
+
+        assert_eq!(decompress_yaz0(&LzFormat::Yaz0.compress(&[]).unwrap()), vec![]);
+    }
+}
+
+#[test]
+fn dol_header() {
+    use binwrite::BinWrite;
+    use binwrite::writers::dol::{DolFile, DolSection};
+
+    let section = |address, len| DolSection { address, data: vec![0xAA; len] };
+    let dol = DolFile {
+        text: vec![section(0x8000_3100, 0x24), section(0x8000_4000, 4)],
+        data: vec![section(0x8000_5000, 8)],
+        bss_address: 0x8000_6000,
+        bss_size: 0x40,
+        entry_point: 0x8000_3100,
+    };
+    let mut bytes = vec![];
+    dol.write(&mut bytes).unwrap();
+
+    let word = |offset: usize| u32::from_be_bytes([bytes[offset], bytes[offset + 1], bytes[offset + 2], bytes[offset + 3]]);
+    assert_eq!((word(0x00), word(0x04), word(0x1C)), (0x100, 0x140, 0x160));
+    assert_eq!((word(0x48), word(0x64)), (0x8000_3100, 0x8000_5000));
+    assert_eq!((word(0x90), word(0xAC)), (0x24, 8));
+    assert_eq!((word(0xD8), word(0xDC), word(0xE0)), (0x8000_6000, 0x40, 0x8000_3100));
+    assert_eq!(bytes.len(), 0x180);
+
+    let too_many = DolFile { text: vec![section(0, 4); 8], ..Default::default() };
+    assert!(too_many.write(&mut vec![]).is_err());
+}
+
+#[test]
+fn rel_header_versions() {
+    use binwrite::BinWrite;
+    use binwrite::writers::dol::RelHeader;
+
+    for version in 1..=3 {
+        let header = RelHeader { version, ..Default::default() };
+        let mut bytes = vec![];
+        header.write(&mut bytes).unwrap();
+        assert_eq!(bytes.len(), header.size());
+    }
+}
+
+#[test]
+fn n64_crc() {
+    use binwrite::writers::rom::{fix_n64_crc, n64_crc, Cic};
+
+    // With an all-zero body, only the seed-dependent accumulator changes
+    let mut rom = vec![0u8; 0x10_1000];
+    let seed = 0xF8CA_4DDCu32;
+    assert_eq!(n64_crc(&rom, Cic::Cic6102).unwrap(), (seed, seed.wrapping_mul(0x4_0001)));
+
+    rom[0x1000] = 0x80;
+    fix_n64_crc(&mut rom, Cic::Cic6102).unwrap();
+    assert_eq!(&rom[0x10..0x18], {
+        let (crc1, crc2) = n64_crc(&rom, Cic::Cic6102).unwrap();
+        [crc1.to_be_bytes(), crc2.to_be_bytes()].concat()
+    }.as_slice());
+
+    assert!(n64_crc(&rom[..0x1000], Cic::Cic6102).is_err());
+}
+
+#[test]
+fn snes_checksum_mirroring() {
+    use binwrite::writers::rom::snes_checksum;
+
+    // A 1.5 MiB ROM checksums as 1 MiB followed by the last 0.5 MiB twice
+    let mut rom = vec![0u8; 0x18_0000];
+    rom[0] = 1;
+    rom[0x10_0000] = 1;
+    assert_eq!(snes_checksum(&rom), 3);
+}
+
+#[test]
+fn texture_swizzling() {
+    use binwrite::BinWrite;
+    use binwrite::writers::{Swizzle, Swizzled};
+
+    let linear: Vec<u8> = (0..8).collect();
+    let morton = Swizzle::Morton { width: 4, height: 2, bytes_per_pixel: 1 };
+    assert_eq!(morton.apply(&linear).unwrap(), [0, 1, 4, 5, 2, 3, 6, 7]);
+    assert!(Swizzle::Morton { width: 3, height: 2, bytes_per_pixel: 1 }.apply(&linear[..6]).is_err());
+
+    let linear: Vec<u8> = (0..=255).collect();
+    let psp = Swizzle::Psp { width: 16, height: 8, bytes_per_pixel: 2 }.apply(&linear).unwrap();
+    assert_eq!(&psp[..20], &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 32, 33, 34, 35]);
+    assert_eq!(&psp[128..132], &[16, 17, 18, 19]);
+
+    let linear: Vec<u8> = (0..512).map(|i| (i / 64) as u8 * 16 + (i % 64) as u8 / 16).collect();
+    let gob = Swizzle::BlockLinear { width: 64, height: 8, bytes_per_pixel: 1, block_height: 1 }.apply(&linear).unwrap();
+    assert_eq!(gob.len(), 512);
+    // (row, 16 byte column) of each 16 byte run within the GOB
+    let runs: Vec<u8> = gob.chunks(16).take(8).map(|run| run[0]).collect();
+    assert_eq!(runs, [0x00, 0x10, 0x01, 0x11, 0x20, 0x30, 0x21, 0x31]);
+
+    let mut bytes = vec![];
+    Swizzled::new(&vec![0u8; 32 * 4], Swizzle::Ps2Psmt8 { width: 32, height: 4 })
+        .write(&mut bytes)
+        .unwrap();
+    assert_eq!(bytes.len(), 128);
+}
+
+#[test]
+fn matrix_and_quaternion_order() {
+    use binwrite::writers::{column_major, quaternion_wxyz, quaternion_xyzw, row_major};
+    use binwrite::WriterOption;
+
+    let matrix = [[1u8, 2, 3], [4, 5, 6]];
+    let mut bytes = vec![];
+    row_major(&matrix, &mut bytes, &WriterOption::default()).unwrap();
+    assert_eq!(bytes, [1, 2, 3, 4, 5, 6]);
+
+    let mut bytes = vec![];
+    column_major(&matrix, &mut bytes, &WriterOption::default()).unwrap();
+    assert_eq!(bytes, [1, 4, 2, 5, 3, 6]);
+
+    let quaternion = [1u8, 2, 3, 4];
+    let mut bytes = vec![];
+    quaternion_xyzw(&quaternion, &mut bytes, &WriterOption::default()).unwrap();
+    quaternion_wxyz(&quaternion, &mut bytes, &WriterOption::default()).unwrap();
+    assert_eq!(bytes, [1, 2, 3, 4, 4, 1, 2, 3]);
+}
+
+#[test]
+fn constants_table() {
+    use binwrite::BinWrite;
+    use binwrite::writers::{ConstantsTable, NamedConstants};
+
+    #[derive(Clone, Copy)]
+    enum Kind {
+        Mesh = 1,
+        Bone = 7,
+    }
+
+    impl BinWrite for Kind {
+        fn write_options<W: std::io::Write>(&self, writer: &mut W, options: &binwrite::WriterOption) -> std::io::Result<()> {
+            (*self as u16).write_options(writer, options)
+        }
+    }
+
+    impl NamedConstants for Kind {
+        fn constants() -> &'static [(Self, &'static str)] {
+            &[(Kind::Mesh, "mesh"), (Kind::Bone, "bone")]
+        }
+    }
+
+    let mut bytes = vec![];
+    ConstantsTable::<Kind, u8>::of()
+        .write_options(&mut bytes, &binwrite::writer_option_new!(endian: binwrite::Endian::Big))
+        .unwrap();
+    assert_eq!(bytes, b"\x00\x01\x06\x00\x07\x0bmesh\0bone\0");
+
+    let mut bytes = vec![];
+    assert!(ConstantsTable::<u8, u8>::new(&[(0, "a\0b")]).write(&mut bytes).is_err());
+}
+
+#[test]
+fn finalize_hook() {
+    use binwrite::BinWrite;
+    use binwrite::writers::finalized;
+
+    // Backfill the total length into the first byte and append an end marker
+    let hook = |_: &Vec<u8>, bytes: &mut Vec<u8>, _: &binwrite::WriterOption| {
+        bytes.extend_from_slice(b"END");
+        bytes[0] = bytes.len() as u8;
+        Ok(())
+    };
+    let mut bytes = vec![];
+    (finalized(hook))(&vec![0u8, 1, 2]).write(&mut bytes).unwrap();
+    assert_eq!(bytes, b"\x06\x01\x02END");
+}
+
+#[test]
+fn prepare_hook() {
+    use binwrite::BinWrite;
+    use binwrite::writers::prepared;
+
+    let entries = vec![3u8, 1, 2];
+    let mut bytes = vec![];
+    (prepared(|entries: &mut Vec<u8>| entries.sort()))(&entries).write(&mut bytes).unwrap();
+    assert_eq!(bytes, [1, 2, 3]);
+    assert_eq!(entries, [3, 1, 2]);
+}
+
+#[test]
+fn best_of_candidates() {
+    use binwrite::{BinWrite, SnapshotBuffer, WriterOption};
+    use binwrite::writers::{BestOf, Candidate};
+
+    fn raw(data: &[u8], writer: &mut SnapshotBuffer, options: &WriterOption) -> std::io::Result<()> {
+        (0u8, data).write_options(writer, options)
+    }
+
+    fn rle(data: &[u8], writer: &mut SnapshotBuffer, options: &WriterOption) -> std::io::Result<()> {
+        1u8.write_options(writer, options)?;
+        for run in data.chunk_by(|a, b| a == b) {
+            (run.len() as u8, run[0]).write_options(writer, options)?;
+        }
+        Ok(())
+    }
+
+    let candidates: [Candidate<[u8]>; 2] = [raw, rle];
+
+    let mut bytes = vec![];
+    BestOf::new(&[7u8; 10][..], &candidates).write(&mut bytes).unwrap();
+    assert_eq!(bytes, [1, 10, 7]);
+
+    let mut bytes = vec![];
+    BestOf::new(&[1u8, 2, 3][..], &candidates).write(&mut bytes).unwrap();
+    assert_eq!(bytes, [0, 1, 2, 3]);
+
+    // Prefer the output with the most zero bytes instead
+    let zeros = |bytes: &[u8]| bytes.iter().filter(|byte| **byte != 0).count() as u64;
+    let (index, _) = BestOf::scored(&[0u8; 4][..], &candidates, zeros).select(&Default::default()).unwrap();
+    assert_eq!(index, 0);
+}
+
+#[test]
+fn format_version() {
+    use binwrite::BinWrite;
+    use binwrite::writers::{versioned, FormatVersion};
+
+    struct Header(u8);
+
+    impl BinWrite for Header {
+        fn write_options<W: std::io::Write>(&self, writer: &mut W, options: &binwrite::WriterOption) -> std::io::Result<()> {
+            self.0.write_options(writer, options)
+        }
+    }
+
+    impl FormatVersion for Header {
+        const FORMAT_VERSION: u32 = 2;
+    }
+
+    let mut bytes = vec![];
+    versioned(&Header(0xAA))
+        .write_options(&mut bytes, &binwrite::writer_option_new!(endian: binwrite::Endian::Big))
+        .unwrap();
+    assert_eq!(bytes, [0, 0, 0, 2, 0xAA]);
+}
+
+#[test]
+fn bit_writer_alignment() {
+    use std::io::Write;
+    use binwrite::writers::{BitOrder, BitWriter};
+
+    let mut bytes = vec![];
+    let mut bits = BitWriter::new(&mut bytes, BitOrder::Lsb0);
+    bits.write_bits(0b11, 2).unwrap();
+    bits.write_bits(0b1, 1).unwrap();
+    assert!(bits.write_all(&[0xFF]).is_err());
+    assert!(bits.write_bits(4, 2).is_err());
+    bits.align(8).unwrap();
+    bits.write_all(&[0xFF]).unwrap();
+    bits.write_bits(0x1F, 5).unwrap();
+    assert_eq!(bits.bit_pos(), 21);
+    bits.finish().unwrap();
+    assert_eq!(bytes, [0b0000_0111, 0xFF, 0b0001_1111]);
+}
+
+#[test]
+fn payload_encoder_from_options() {
+    use std::io::Write;
+    use std::sync::{Arc, Mutex};
+    use binwrite::BinWrite;
+    use binwrite::writers::{payload_encoded, PayloadEncoder};
+
+    // Delta-codes bytes, counting how many payloads it has finished
+    struct Delta {
+        previous: u8,
+        payloads: u8,
+    }
+
+    impl PayloadEncoder for Delta {
+        fn encode(&mut self, data: &[u8], out: &mut dyn Write) -> std::io::Result<()> {
+            for byte in data {
+                out.write_all(&[byte.wrapping_sub(self.previous)])?;
+                self.previous = *byte;
+            }
+            Ok(())
+        }
+
+        fn finish(&mut self, out: &mut dyn Write) -> std::io::Result<()> {
+            self.previous = 0;
+            self.payloads += 1;
+            out.write_all(&[0xF0 | self.payloads])
+        }
+    }
+
+    let encoder = Arc::new(Mutex::new(Delta { previous: 0, payloads: 0 }));
+    let options = binwrite::writer_option_new!(payload_encoder: Some(encoder.clone()));
+
+    let mut bytes = vec![];
+    payload_encoded(&(1u8, [3u8, 6])).write_options(&mut bytes, &options).unwrap();
+    payload_encoded(&[5u8, 5]).write_options(&mut bytes, &options).unwrap();
+    assert_eq!(bytes, [1, 2, 3, 0xF1, 5, 0, 0xF2]);
+
+    assert!(payload_encoded(&1u8).write(&mut vec![]).is_err());
+
+    // Nesting is an error instead of a deadlock on the encoder
+    assert!(payload_encoded(&payload_encoded(&1u8)).write_options(&mut vec![], &options).is_err());
+}
+
+#[test]
+fn localized_utf16_strings() {
+    use std::collections::BTreeMap;
+    use binwrite::BinWrite;
+    use binwrite::writers::{LocalizedStrings, TextEncoding};
+
+    let mut names = BTreeMap::new();
+    names.insert(1u8, "ab");
+    names.insert(2, "c");
+    names.insert(3, "ab");
+
+    let mut bytes = vec![];
+    LocalizedStrings::<_, _, u16>::new(&names, TextEncoding::Utf16, true)
+        .write_options(&mut bytes, &binwrite::writer_option_new!(endian: binwrite::Endian::Big))
+        .unwrap();
+    // The 11 byte header is padded to 12 so the UTF-16 pool is aligned, and "ab" is stored once
+    assert_eq!(bytes, [
+        0, 3, 1, 0, 12, 2, 0, 18, 3, 0, 12, 0,
+        0, b'a', 0, b'b', 0, 0, 0, b'c', 0, 0,
+    ]);
+}
+
+#[test]
+fn ply_mesh() {
+    use binwrite::BinWrite;
+    use binwrite::writers::mesh::PlyMesh;
+
+    let vertices = [[0.0f32, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]];
+    let faces = [vec![0u32, 1, 2]];
+    let mut bytes = vec![];
+    PlyMesh::new(&vertices, &faces)
+        .write_options(&mut bytes, &binwrite::writer_option_new!(endian: binwrite::Endian::Little))
+        .unwrap();
+
+    let header = "ply\nformat binary_little_endian 1.0\nelement vertex 3\nproperty float x\n\
+        property float y\nproperty float z\nelement face 1\n\
+        property list uchar uint vertex_indices\nend_header\n";
+    assert_eq!(&bytes[..header.len()], header.as_bytes());
+    let body = &bytes[header.len()..];
+    assert_eq!(body.len(), 3 * 12 + 1 + 3 * 4);
+    assert_eq!(&body[12..16], &1.0f32.to_le_bytes());
+    assert_eq!(&body[36..], &[3, 0, 0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0]);
+}
+
+#[test]
+fn chunk_scope_crc() {
+    use std::io::{Cursor, Write};
+    use binwrite::checksum::Crc;
+    use binwrite::writers::ChunkScope;
+
+    let options = binwrite::writer_option_new!(endian: binwrite::Endian::Big);
+    let mut writer = Cursor::new(vec![]);
+    let mut chunk = ChunkScope::begin_with_crc(&mut writer, b"IDAT", Crc::CRC32, &options).unwrap();
+    chunk.write_all(b"abc").unwrap();
+    chunk.end().unwrap();
+
+    let bytes = writer.into_inner();
+    assert_eq!(&bytes[..11], b"IDAT\0\0\0\x03abc");
+    assert_eq!(&bytes[11..15], &0x352441C2u32.to_be_bytes());
+    // Odd-sized data is padded after the CRC
+    assert_eq!(bytes.len(), 16);
+}
+
+#[test]
+fn fletcher_and_internet_checksums() {
+    use binwrite::BinWrite;
+    use binwrite::checksum::{checksum_filled, Checksum, ChecksumAppended};
+    use binwrite::packet::{FieldSize, PacketBuilder, PseudoHeader, Scope};
+
+    assert_eq!(Checksum::Fletcher16.checksum(b"abcde").unwrap(), 0xC8F0);
+    assert_eq!(Checksum::Fletcher16.checksum(b"abcdef").unwrap(), 0x2057);
+    assert_eq!(Checksum::Fletcher32.checksum(b"abcde").unwrap(), 0xF04FC729);
+    assert_eq!(Checksum::Fletcher32.checksum(b"abcdef").unwrap(), 0x56502D2A);
+
+    let options = binwrite::writer_option_new!(endian: binwrite::Endian::Big);
+    let mut bytes = vec![];
+    ChecksumAppended::new(&b"abcde"[..], Checksum::Fletcher16).write_options(&mut bytes, &options).unwrap();
+    assert_eq!(bytes, b"abcde\xc8\xf0");
+
+    // A UDP datagram checksummed over its pseudo-header matches the packet builder
+    let pseudo_header = PseudoHeader::Ipv4 { src: [10, 0, 0, 1], dst: [10, 0, 0, 2], protocol: 17 };
+    let datagram = (1234u16, 5678u16, 13u16, 0u16, *b"hello");
+    let mut bytes = vec![];
+    checksum_filled(Checksum::Internet(Some(pseudo_header)), 6)(&datagram)
+        .write_options(&mut bytes, &options)
+        .unwrap();
+
+    let mut packet = PacketBuilder::new();
+    packet.push(&(1234u16, 5678u16, 0u16, 0u16)).unwrap()
+        .length(4, FieldSize::U16, Scope::WholeLayer)
+        .checksum(6, Scope::WholeLayer, Some(pseudo_header));
+    packet.payload(b"hello");
+    assert_eq!(bytes, packet.build().unwrap());
+
+    assert!(checksum_filled(Checksum::Internet(None), 4)(&0u32).write_options(&mut vec![], &options).is_err());
+}